@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 
+use crate::config::types::SubmoduleMode;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "gwe",
@@ -34,8 +36,32 @@ pub enum Command {
     List(ListCommand),
     /// worktree を削除
     Rm(RmCommand),
+    /// マージ済み・追跡先が消えた・放置された worktree をまとめて削除
+    Prune(PruneCommand),
+    /// 削除した worktree の一覧・整理
+    Trash(TrashCommand),
+    /// 削除した worktree をゴミ箱から元のパスに復元
+    Restore(RestoreCommand),
+    /// worktree の管理情報を診断・修復
+    Doctor(DoctorCommand),
+    /// 管理外の worktree を gwe の配置へ移動して取り込む
+    Import(ImportCommand),
+    /// base_dir や配置の変更に合わせて管理下の worktree を移動
+    Relocate(RelocateCommand),
+    /// worktree のブランチ名を変え、ディレクトリも新しい名前の配置へ移動
+    Mv(MvCommand),
+    /// worktree をロックして rm・prune から守る
+    Lock(LockCommand),
+    /// worktree のロックを解除
+    Unlock(UnlockCommand),
     /// 指定 worktree の絶対パスを出力
     Cd(CdCommand),
+    /// 未コミット変更を別の worktree へ移動 (またはコピー)
+    Transfer(TransferCommand),
+    /// worktree の状態 (未コミット変更・ignore ファイル) ごと新しいブランチへ複製
+    Fork(ForkCommand),
+    /// グループの worktree でツールを分割ペインに再度起動
+    Open(OpenCommand),
     /// シェル統合をプロファイルにインストール
     Init(InitCommand),
     /// シェル初期化スクリプトを出力
@@ -67,29 +93,88 @@ pub enum Command {
     Cli(ToolCommand),
 }
 
-#[derive(Args, Debug, Clone)]
+#[derive(Args, Debug, Clone, Default)]
 pub struct ToolCommand {
     /// 対象 worktree (または新規作成時のターゲット)
     #[arg(value_name = "WORKTREE")]
     pub target: Option<String>,
     
-    /// 新規ブランチ名 (指定された場合、新規 worktree を作成して開く)
-    #[arg(short = 'b', long = "branch", value_name = "BRANCH")]
-    pub branch: Option<String>,
+    /// 新規ブランチ名 (指定された場合、新規 worktree を作成して開く。値を省略すると自動生成。
+    /// 複数指定するとそれぞれの worktree を作成)
+    #[arg(id = "branch", short = 'b', long = "branch", value_name = "BRANCH", num_args = 0..=1, default_missing_value = "")]
+    pub branches: Vec<String>,
+
+    /// 説明文からブランチ名を生成 ("Fix login timeout" → fix/login-timeout)
+    #[arg(long = "name-from", value_name = "TEXT", conflicts_with_all = ["branch", "track", "detach", "orphan"])]
+    pub name_from: Option<String>,
 
     /// 追跡する remote/branch (新規作成時用)
     #[arg(long = "track", value_name = "REMOTE/BRANCH")]
     pub track: Option<String>,
 
-    /// 並列 worktree 作成数 (1-5、分割ペインで起動)
-    #[arg(short = 'x', long = "multiplier", value_name = "COUNT", value_parser = clap::value_parser!(u8).range(1..=5))]
+    /// 並列 worktree 作成数 (分割ペインで起動する場合は 1-5)
+    #[arg(short = 'x', long = "multiplier", value_name = "COUNT", value_parser = clap::value_parser!(u8).range(1..))]
     pub multiplier: Option<u8>,
 
+    /// 複数 worktree 作成時に同時に準備 (フック実行) する数 (省略時は CPU 数)
+    #[arg(short = 'j', long = "jobs", value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+    pub jobs: Option<u16>,
+
+    /// 複数作成時のグループ名 (省略時はブランチ名の共通部分)
+    #[arg(long = "group", value_name = "GROUP")]
+    pub group: Option<String>,
+
+    /// HEAD を切り離した worktree を作成 (タグや SHA の一時的な確認用)
+    #[arg(long = "detach", conflicts_with_all = ["branch", "track", "orphan", "multiplier"])]
+    pub detach: bool,
+
+    /// 履歴を持たない新規ブランチで worktree を作成 (gh-pages など)
+    #[arg(long = "orphan", value_name = "BRANCH", conflicts_with_all = ["target", "branch", "track", "multiplier"])]
+    pub orphan: Option<String>,
+
+    /// sparse-checkout (cone モード) でチェックアウトするディレクトリ
+    #[arg(long = "sparse", value_name = "DIR", num_args = 1.., conflicts_with = "orphan")]
+    pub sparse: Vec<String>,
+
+    /// gwe.sparse.default やプロファイルの sparse 設定を無視して全体をチェックアウト
+    #[arg(long = "no-sparse", conflicts_with = "sparse")]
+    pub no_sparse: bool,
+
+    /// サブモジュールの初期化方法 (省略時は gwe.submodules)
+    #[arg(long = "submodules", value_name = "MODE", value_enum)]
+    pub submodules: Option<SubmoduleMode>,
+
+    /// 現在の worktree の未コミット変更 (未追跡ファイルを含む) を新しい worktree へ移動
+    #[arg(long = "carry", conflicts_with_all = ["from_stash", "orphan", "multiplier"])]
+    pub carry: bool,
+
+    /// 指定した stash を新しい worktree に適用
+    #[arg(long = "from-stash", value_name = "STASH", conflicts_with = "orphan")]
+    pub from_stash: Option<String>,
+
+    /// 作成した worktree をロック (`--lock=<REASON>` で理由を付ける)
+    #[arg(long = "lock", value_name = "REASON", num_args = 0..=1, require_equals = true, default_missing_value = "")]
+    pub lock: Option<String>,
+
+    /// 作成時に適用するプロファイル (gwe.profile.<NAME>.*)
+    #[arg(long = "profile", value_name = "NAME")]
+    pub profile: Option<String>,
+
     /// ツールに渡す引数
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     pub args: Vec<String>,
 }
 
+impl ToolCommand {
+    /// `-b` が 1 つだけ指定された場合のブランチ名
+    pub fn branch(&self) -> Option<&str> {
+        match self.branches.as_slice() {
+            [branch] => Some(branch.as_str()),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct ConfigCommand {
     #[command(subcommand)]
@@ -126,21 +211,179 @@ pub enum ConfigAction {
     },
 }
 
-#[derive(Args, Debug, Clone, Copy)]
+#[derive(Args, Debug, Clone)]
 pub struct ListCommand {
     /// JSON 形式で出力
     #[arg(long = "json")]
     pub json: bool,
+    /// 指定グループの worktree のみ表示
+    #[arg(long = "group", value_name = "GROUP")]
+    pub group: Option<String>,
 }
 
 #[derive(Args, Debug, Clone)]
 pub struct RmCommand {
-    /// 削除対象の worktree
-    #[arg(value_name = "WORKTREE")]
-    pub target: Option<String>,
+    /// 削除対象の worktree (複数指定や `exp-*` のような glob パターンも可。`.` は現在の worktree)
+    #[arg(value_name = "WORKTREE", conflicts_with_all = ["group", "here"])]
+    pub targets: Vec<String>,
+    /// 現在の worktree を削除し、シェルをメイン worktree へ移動
+    #[arg(long = "here", conflicts_with = "group")]
+    pub here: bool,
+    /// 指定グループの worktree をすべて削除
+    #[arg(long = "group", value_name = "GROUP")]
+    pub group: Option<String>,
+    /// 対応ブランチも削除
+    #[arg(short = 'b', long = "with-branch")]
+    pub with_branch: bool,
+    /// 未コミットの変更や未プッシュのコミットがあっても削除 (2 回指定するとロックされた worktree も削除)
+    #[arg(short = 'f', long = "force", action = ArgAction::Count)]
+    pub force: u8,
+    /// worktree 内で動いているプロセスを終了させてから削除 (SIGTERM、応答がなければ SIGKILL)
+    #[arg(long = "kill")]
+    pub kill: bool,
+    /// ブランチの追跡先のリモートブランチも削除 (`-b` と併用)
+    #[arg(long = "remote", requires = "with_branch")]
+    pub remote: bool,
+    /// 複数削除するときに確認しない
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct PruneCommand {
+    /// デフォルトブランチにマージ済みのもの
+    #[arg(long = "merged")]
+    pub merged: bool,
+    /// 追跡していたリモートブランチが削除されたもの
+    #[arg(long = "gone")]
+    pub gone: bool,
+    /// 指定日数以上活動がないもの
+    #[arg(long = "stale", value_name = "DAYS")]
+    pub stale: Option<u64>,
+    /// ディレクトリが失われ git が prunable としているもの
+    #[arg(long = "prunable")]
+    pub prunable: bool,
     /// 対応ブランチも削除
     #[arg(short = 'b', long = "with-branch")]
     pub with_branch: bool,
+    /// 失われる作業があってもゴミ箱へ退避して削除 (2 回指定するとロックされた worktree も削除)
+    #[arg(short = 'f', long = "force", action = ArgAction::Count)]
+    pub force: u8,
+    /// worktree 内で動いているプロセスを終了させてから削除
+    #[arg(long = "kill")]
+    pub kill: bool,
+    /// 確認せずに削除
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+    /// 計画を表示するだけで削除しない
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct DoctorCommand {
+    /// 見つかった問題を修復 (git worktree repair / prune、残った lock の削除など)
+    #[arg(long = "fix")]
+    pub fix: bool,
+    /// 管理外のディレクトリの扱い (省略時は端末で確認し、端末でなければ残す)
+    #[arg(long = "orphans", value_enum, value_name = "ACTION", requires = "fix")]
+    pub orphans: Option<OrphanAction>,
+}
+
+/// `gwe doctor --fix` で git が知らないディレクトリをどうするか
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "lowercase")]
+pub enum OrphanAction {
+    /// worktree として登録する
+    Import,
+    /// ディレクトリを削除する
+    Delete,
+    /// そのまま残す
+    Keep,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ImportCommand {
+    /// 取り込む worktree のパス
+    #[arg(value_name = "PATH", required_unless_present = "all", conflicts_with = "all")]
+    pub path: Option<PathBuf>,
+    /// 管理外の worktree をすべて取り込む
+    #[arg(long = "all")]
+    pub all: bool,
+    /// 移動せずに計画だけを表示
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RelocateCommand {
+    /// 変更前の base_dir (省略時は `<dir>/<リポジトリ名>/<ブランチ>` の形から推測)
+    #[arg(long = "from", value_name = "DIR")]
+    pub from: Option<PathBuf>,
+    /// gitdir のリンクを相対パスに切り替える (git 2.48 以降)
+    #[arg(long = "relative-paths")]
+    pub relative_paths: bool,
+    /// 確認せずに移動
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+    /// 計画を表示するだけで移動しない
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct MvCommand {
+    /// 対象の worktree (表示名/ブランチ名/ディレクトリ名)
+    #[arg(value_name = "WORKTREE")]
+    pub worktree: String,
+    /// 新しいブランチ名
+    #[arg(value_name = "NEW_BRANCH")]
+    pub new_branch: String,
+    /// 追跡先のリモートブランチも新しい名前に変える
+    #[arg(long = "remote")]
+    pub remote: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct LockCommand {
+    /// 対象の worktree (表示名/ブランチ名/ディレクトリ名)
+    #[arg(value_name = "WORKTREE")]
+    pub worktree: String,
+    /// ロックの理由 (list に表示される)
+    #[arg(long = "reason", value_name = "TEXT")]
+    pub reason: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct UnlockCommand {
+    /// 対象の worktree (表示名/ブランチ名/ディレクトリ名)
+    #[arg(value_name = "WORKTREE")]
+    pub worktree: String,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct TrashCommand {
+    #[command(subcommand)]
+    pub action: TrashAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum TrashAction {
+    /// ゴミ箱の内容を一覧表示
+    List,
+    /// ゴミ箱のエントリを完全に削除
+    Purge {
+        /// 指定期間より前に削除したものだけを対象にする (例: 14d, 12h)
+        #[arg(long = "older-than", value_name = "AGE")]
+        older_than: Option<String>,
+    },
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct RestoreCommand {
+    /// `gwe trash list` に表示される ID
+    #[arg(value_name = "ID")]
+    pub id: String,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -150,6 +393,48 @@ pub struct CdCommand {
     pub target: Option<String>,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct TransferCommand {
+    /// 変更の移動元 worktree
+    #[arg(value_name = "FROM")]
+    pub from: String,
+    /// 変更の移動先 worktree
+    #[arg(value_name = "TO")]
+    pub to: String,
+    /// 移動元の変更を残したままコピー
+    #[arg(long = "copy")]
+    pub copy: bool,
+    /// 対象を限定するパス (`--` の後に指定)
+    #[arg(last = true, value_name = "PATHS")]
+    pub paths: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct OpenCommand {
+    /// 対象グループ
+    #[arg(long = "group", value_name = "GROUP")]
+    pub group: String,
+    /// 記録されたツールの代わりに起動するツール
+    #[arg(long = "tool", value_name = "TOOL")]
+    pub tool: Option<String>,
+    /// ツールに渡す引数
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub args: Vec<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct ForkCommand {
+    /// 複製元の worktree
+    #[arg(value_name = "WORKTREE")]
+    pub source: String,
+    /// 作成するブランチ名
+    #[arg(short = 'b', long = "branch", value_name = "BRANCH")]
+    pub branch: String,
+    /// ignore されたファイルを複製しない
+    #[arg(long = "no-ignored")]
+    pub no_ignored: bool,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct ShellInitCommand {
     /// シェル種別（pwsh/cmd/bash）
@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
 use anyhow::Result;
+use clap::ValueEnum;
 
+use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::GitRunner;
 
-use super::types::{Config, Hook, CommandHook, GlobCopyHook};
+use super::types::{CollisionPolicy, Config, Hook, CommandHook, GlobCopyHook, SubmoduleMode};
 
 pub fn load_config(repo: &RepoContext) -> Result<Config> {
     let mut config = Config::default();
@@ -39,6 +41,19 @@ fn load_from_git_config(repo: &RepoContext, config: &mut Config) -> Result<()> {
             "gwe.worktrees.dir" => {
                 config.defaults.base_dir = PathBuf::from(value);
             }
+            "gwe.worktrees.extradirs" => {
+                // パスには空白が含まれうるので分割せず、複数指定は --add で行う
+                config.defaults.extra_dirs.push(PathBuf::from(value));
+            }
+            "gwe.worktrees.oncollision" => {
+                config.path_collision =
+                    CollisionPolicy::from_str(value.trim(), true).map_err(|_| {
+                        AppError::config(format!(
+                            "invalid value for gwe.worktrees.onCollision: '{}' (expected error or suffix)",
+                            value
+                        ))
+                    })?;
+            }
             "gwe.defaultbranch" => {
                 config.default_branch = Some(value.to_string());
             }
@@ -49,13 +64,46 @@ fn load_from_git_config(repo: &RepoContext, config: &mut Config) -> Result<()> {
                 config.default_cli = Some(value.to_string());
             }
             "gwe.multicli" => {
-                // カンマまたは空白で分割し、トリミングして空でないもののみを追加
-                for v in value.split(|c: char| c == ',' || c.is_whitespace()) {
-                    let v = v.trim();
-                    if !v.is_empty() {
-                        config.multi_cli.push(v.to_string());
-                    }
+                config.multi_cli.extend(split_list(value));
+            }
+            "gwe.sparse.default" => {
+                config.sparse_default.extend(split_list(value));
+            }
+            "gwe.submodules" => {
+                config.submodules = SubmoduleMode::from_str(value.trim(), true).map_err(|_| {
+                    AppError::config(format!(
+                        "invalid value for gwe.submodules: '{}' (expected none, init or recursive)",
+                        value
+                    ))
+                })?;
+            }
+            "gwe.branch.prefix" => {
+                config.branch_prefixes.extend(split_list(value));
+            }
+            "gwe.branch.pattern" => {
+                crate::worktree::naming::compile_pattern(value)?;
+                config.branch_pattern = Some(value.to_string());
+            }
+            "gwe.multi.suffix" => {
+                if !value.contains("{n}") && !value.contains("{0n}") {
+                    return Err(AppError::config(format!(
+                        "invalid value for gwe.multi.suffix: '{}' (must contain {{n}} or {{0n}})",
+                        value
+                    ))
+                    .into());
                 }
+                config.multi_suffix = value.to_string();
+            }
+            "gwe.rm.force" => {
+                config.rm_force = parse_bool(value).ok_or_else(|| {
+                    AppError::config(format!(
+                        "invalid value for gwe.rm.force: '{}' (expected true or false)",
+                        value
+                    ))
+                })?;
+            }
+            "gwe.rm.fallback" => {
+                config.rm_fallback = Some(value.to_string());
             }
             "gwe.copy.include" => {
                 config.hooks.post_create.push(Hook::GlobCopy(GlobCopyHook {
@@ -70,13 +118,49 @@ fn load_from_git_config(repo: &RepoContext, config: &mut Config) -> Result<()> {
                     work_dir: None,
                 }));
             }
-            _ => {}
+            _ => {
+                if let Some((name, field)) = parse_profile_key(key) {
+                    let profile = config.profiles.entry(name.to_string()).or_default();
+                    if field == "sparse" {
+                        profile.sparse.extend(split_list(value));
+                    }
+                }
+            }
         }
     }
 
     Ok(())
 }
 
+/// git config の真偽値 (true/false, yes/no, on/off, 1/0) を解釈する
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" | "" => Some(false),
+        _ => None,
+    }
+}
+
+/// カンマまたは空白で分割し、トリミングして空でないもののみを返す
+fn split_list(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// `gwe.profile.<name>.<field>` を (name, field) に分解する。
+/// サブセクション名は git が大文字小文字を保持するのでそのまま使う。
+fn parse_profile_key(key: &str) -> Option<(&str, &str)> {
+    let rest = key.strip_prefix("gwe.profile.")?;
+    let (name, field) = rest.rsplit_once('.')?;
+    if name.is_empty() {
+        return None;
+    }
+    Some((name, field))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +191,37 @@ mod tests {
         assert_eq!(config.default_branch, Some("main".to_string()));
     }
 
+    #[test]
+    fn rejects_unknown_submodule_mode() {
+        let (_dir, repo) = temp_repo();
+        run_git(repo.worktree_root(), &["config", "gwe.submodules", "always"]);
+
+        let err = load_config(&repo).expect_err("invalid mode");
+        assert!(err.to_string().contains("gwe.submodules"));
+    }
+
+    #[test]
+    fn loads_sparse_default_and_profiles() {
+        let (_dir, repo) = temp_repo();
+        run_git(repo.worktree_root(), &["config", "gwe.sparse.default", "src, docs"]);
+        run_git(repo.worktree_root(), &["config", "gwe.profile.Web.sparse", "web"]);
+
+        let config = load_config(&repo).expect("load config");
+
+        assert_eq!(config.sparse_default, vec!["src", "docs"]);
+        let profile = config.profile("Web").expect("profile keeps its case");
+        assert_eq!(profile.sparse, vec!["web"]);
+    }
+
+    #[test]
+    fn rejects_invalid_branch_pattern() {
+        let (_dir, repo) = temp_repo();
+        run_git(repo.worktree_root(), &["config", "gwe.branch.pattern", "feature/("]);
+
+        let err = load_config(&repo).expect_err("invalid pattern");
+        assert!(err.to_string().contains("gwe.branch.pattern"));
+    }
+
     fn temp_repo() -> (TempDir, RepoContext) {
         let dir = TempDir::new().expect("temp repo");
         init_git(dir.path());
@@ -1,10 +1,12 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
+use clap::ValueEnum;
 use serde::Deserialize;
 
 pub(crate) const DEFAULT_VERSION: &str = "1.0";
 pub(crate) const DEFAULT_BASE_DIR: &str = "../worktree";
+pub(crate) const DEFAULT_MULTI_SUFFIX: &str = "-{n}";
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -22,6 +24,24 @@ pub struct Config {
     pub default_cli: Option<String>,
     #[serde(default)]
     pub multi_cli: Vec<String>,
+    #[serde(default)]
+    pub sparse_default: Vec<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    #[serde(default)]
+    pub submodules: SubmoduleMode,
+    #[serde(default)]
+    pub path_collision: CollisionPolicy,
+    #[serde(default)]
+    pub branch_prefixes: Vec<String>,
+    #[serde(default)]
+    pub branch_pattern: Option<String>,
+    #[serde(default = "default_multi_suffix")]
+    pub multi_suffix: String,
+    #[serde(default)]
+    pub rm_force: bool,
+    #[serde(default)]
+    pub rm_fallback: Option<String>,
 }
 
 impl Default for Config {
@@ -34,6 +54,15 @@ impl Default for Config {
             default_editor: None,
             default_cli: None,
             multi_cli: Vec::new(),
+            sparse_default: Vec::new(),
+            profiles: BTreeMap::new(),
+            submodules: SubmoduleMode::default(),
+            path_collision: CollisionPolicy::default(),
+            branch_prefixes: Vec::new(),
+            branch_pattern: None,
+            multi_suffix: default_multi_suffix(),
+            rm_force: false,
+            rm_fallback: None,
         }
     }
 }
@@ -42,33 +71,90 @@ impl Config {
     pub fn resolved_base_dir(&self, repo_root: &Path) -> PathBuf {
         self.defaults.resolve_base_dir(repo_root)
     }
+
+    /// gwe が管理する worktree を置くディレクトリ (base_dir と `gwe.worktrees.extraDirs`)
+    pub fn managed_roots(&self, repo_root: &Path) -> Vec<PathBuf> {
+        let mut roots = vec![self.resolved_base_dir(repo_root)];
+        roots.extend(
+            self.defaults
+                .extra_dirs
+                .iter()
+                .map(|dir| resolve_dir(dir, repo_root)),
+        );
+        roots
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.get(name)
+    }
+}
+
+/// 新規 worktree でのサブモジュール初期化方法 (`gwe.submodules`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+#[clap(rename_all = "lowercase")]
+pub enum SubmoduleMode {
+    #[default]
+    None,
+    Init,
+    Recursive,
+}
+
+/// 別ブランチが同じ worktree パスになる場合の扱い (`gwe.worktrees.onCollision`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+#[clap(rename_all = "lowercase")]
+pub enum CollisionPolicy {
+    #[default]
+    Error,
+    Suffix,
+}
+
+/// `gwe.profile.<name>.*` で定義される作成時設定のまとまり
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub sparse: Vec<String>,
 }
 
 fn default_version() -> String {
     DEFAULT_VERSION.to_owned()
 }
 
+fn default_multi_suffix() -> String {
+    DEFAULT_MULTI_SUFFIX.to_owned()
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Defaults {
     #[serde(default = "default_base_dir")]
     pub base_dir: PathBuf,
+    /// 移動せずに管理下とみなす追加のディレクトリ (`gwe.worktrees.extraDirs`)
+    #[serde(default)]
+    pub extra_dirs: Vec<PathBuf>,
 }
 
 impl Default for Defaults {
     fn default() -> Self {
         Self {
             base_dir: default_base_dir(),
+            extra_dirs: Vec::new(),
         }
     }
 }
 
 impl Defaults {
     pub fn resolve_base_dir(&self, repo_root: &Path) -> PathBuf {
-        if self.base_dir.is_absolute() {
-            normalize_fs_path(&self.base_dir)
-        } else {
-            normalize_fs_path(repo_root).join(&self.base_dir)
-        }
+        resolve_dir(&self.base_dir, repo_root)
+    }
+}
+
+/// 相対パスはリポジトリのルートから解決する
+pub fn resolve_dir(dir: &Path, repo_root: &Path) -> PathBuf {
+    if dir.is_absolute() {
+        normalize_fs_path(dir)
+    } else {
+        normalize_fs_path(repo_root).join(dir)
     }
 }
 
@@ -9,6 +9,7 @@ use anyhow::{Context, Result, anyhow};
 pub struct RepoContext {
     worktree_root: PathBuf,
     main_root: PathBuf,
+    common_dir: PathBuf,
     repo_name: String,
 }
 
@@ -44,6 +45,7 @@ impl RepoContext {
         Ok(Self {
             worktree_root,
             main_root,
+            common_dir,
             repo_name,
         })
     }
@@ -56,6 +58,11 @@ impl RepoContext {
         &self.main_root
     }
 
+    /// 全 worktree で共有される git ディレクトリ (`git rev-parse --git-common-dir`)
+    pub fn common_dir(&self) -> &Path {
+        &self.common_dir
+    }
+
     pub fn repo_name(&self) -> &str {
         &self.repo_name
     }
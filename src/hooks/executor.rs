@@ -151,6 +151,7 @@ impl<'a> HookExecutor<'a> {
         Ok(())
     }
 
+    #[allow(clippy::collapsible_if)]
     fn is_excluded(&self, relative_path: &Path) -> bool {
         // .git ディレクトリは常に除外
         for component in relative_path.components() {
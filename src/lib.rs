@@ -27,7 +27,10 @@ pub fn run() -> Result<ExitCode> {
                 &repo,
                 &git,
                 &config,
-                worktree::list::ListOptions { json: cmd.json },
+                worktree::list::ListOptions {
+                    json: cmd.json,
+                    group: cmd.group,
+                },
             )?;
         }
         cli::Command::Rm(cmd) => {
@@ -36,12 +39,81 @@ pub fn run() -> Result<ExitCode> {
             let git = git::GitRunner::new(repo.clone());
             worktree::rm::run(&repo, &git, &config, &cmd)?;
         }
+        cli::Command::Prune(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::prune::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Doctor(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::doctor::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Import(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::import::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Relocate(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::relocate::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Mv(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::mv::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Lock(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::lock::lock(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Unlock(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::lock::unlock(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Trash(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::trash::run(&repo, &git, &cmd)?;
+        }
+        cli::Command::Restore(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::trash::restore(&repo, &git, &cmd)?;
+        }
         cli::Command::Cd(cmd) => {
             let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
             let config = config::load_config(&repo)?;
             let git = git::GitRunner::new(repo.clone());
             worktree::resolve::run(&repo, &git, &config, cmd.target)?;
         }
+        cli::Command::Transfer(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::transfer::run(&repo, &git, &config, &cmd)?;
+        }
+        cli::Command::Open(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let git = git::GitRunner::new(repo);
+            worktree::tool::run_open_group(&git, &cmd)?;
+        }
+        cli::Command::Fork(cmd) => {
+            let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
+            let config = config::load_config(&repo)?;
+            let git = git::GitRunner::new(repo.clone());
+            worktree::fork::run(&repo, &git, &config, &cmd)?;
+        }
         cli::Command::Config(cmd) => {
             let repo = git::rev::RepoContext::discover(globals.repo.clone())?;
             shell::config::run(&repo, cmd)?;
@@ -1,5 +1,17 @@
 pub fn script() -> String {
     r#"
+export GWE_SHELL_INTEGRATION=1
+
+__gwe_rm_here() {
+    local arg
+    for arg in "$@"; do
+        case "$arg" in
+            --here|.) return 0 ;;
+        esac
+    done
+    return 1
+}
+
 gwe() {
     if [ "$1" = "cd" ]; then
         local dest
@@ -10,6 +22,18 @@ gwe() {
         else
             return $ret
         fi
+    elif [ "$1" = "rm" ] && __gwe_rm_here "${@:2}"; then
+        # 削除する worktree から先に出て、削除後の移動先を受け取る
+        local origin="$PWD" dest
+        cd "$(command gwe cd @)" || return
+        dest=$(command gwe --repo "$origin" "$@")
+        local ret=$?
+        if [ $ret -eq 0 ] && [ -n "$dest" ]; then
+            cd "$dest"
+        else
+            cd "$origin" 2>/dev/null
+            return $ret
+        fi
     else
         command gwe "$@"
     fi
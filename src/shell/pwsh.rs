@@ -1,4 +1,5 @@
 const SCRIPT: &str = r#"$ErrorActionPreference = 'Stop'
+$env:GWE_SHELL_INTEGRATION = '1'
 
 function Get-GweExePath {
     $cmd = Get-Command gwe.exe -ErrorAction SilentlyContinue
@@ -17,6 +18,23 @@ function Get-GweExePath {
 
 function gwe {
     $exe = Get-GweExePath
+
+    $rest = @($args | Select-Object -Skip 1)
+    if ($args.Count -gt 0 -and $args[0] -eq 'rm' -and ($rest -contains '--here' -or $rest -contains '.')) {
+        # 削除する worktree から先に出て、削除後の移動先を受け取る
+        $origin = (Get-Location).Path
+        Set-Location ((& $exe cd '@') | Select-Object -Last 1).Trim()
+        $output = & $exe --repo $origin @args
+        $exitCode = $LASTEXITCODE
+        if ($exitCode -eq 0 -and $output) {
+            Set-Location ($output | Select-Object -Last 1).Trim()
+        } else {
+            Set-Location $origin -ErrorAction SilentlyContinue
+        }
+        $global:LASTEXITCODE = $exitCode
+        return
+    }
+
     $output = & $exe @args
     $exitCode = $LASTEXITCODE
 
@@ -37,7 +55,7 @@ function gwe {
 Register-ArgumentCompleter -Native -CommandName gwe -ScriptBlock {
     param($commandName, $parameterName, $wordToComplete, $commandAst, $fakeBoundParameters)
 
-    $commands = @('add','list','rm','cd','init','shell-init','config','cursor','wind','anti','claude','codex','gemini','cli','-e','-c')
+    $commands = @('add','list','rm','prune','trash','restore','doctor','import','relocate','mv','lock','unlock','cd','transfer','fork','open','init','shell-init','config','cursor','wind','anti','claude','codex','gemini','cli','-e','-c')
     $elements = @($commandAst.CommandElements | ForEach-Object { $_.Extent.Text })
 
     if ($elements.Count -lt 2) {
@@ -51,7 +69,7 @@ Register-ArgumentCompleter -Native -CommandName gwe -ScriptBlock {
 
     $subcommand = $elements[1]
 
-    if ($subcommand -in @('cd','rm','cursor','wind','anti','claude','codex','gemini','cli','-e','-c')) {
+    if ($subcommand -in @('cd','rm','transfer','fork','cursor','wind','anti','claude','codex','gemini','cli','-e','-c')) {
         $exe = Get-GweExePath
         $json = & $exe list --json 2>$null
         if (-not $?) {
@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use crate::worktree::{common, metadata};
+
+/// worktree 削除後の後始末に渡す情報
+#[derive(Debug, Clone)]
+pub struct CleanupContext {
+    /// 削除した worktree のパス
+    pub worktree_path: PathBuf,
+    /// 削除前の worktree の管理ディレクトリ (`<common-dir>/worktrees/<id>`)
+    pub admin_dir: Option<PathBuf>,
+    /// worktree のベースディレクトリ。これより上のディレクトリには触れない
+    pub base_dir: PathBuf,
+}
+
+impl CleanupContext {
+    /// 管理ディレクトリは削除と一緒に消えるため、削除前に作る
+    pub fn capture(worktree_path: &Path, base_dir: &Path) -> Self {
+        let worktree_path = common::normalize_path(worktree_path);
+        Self {
+            admin_dir: metadata::admin_dir(&worktree_path),
+            worktree_path,
+            base_dir: common::normalize_path(base_dir),
+        }
+    }
+}
+
+type CleanupFn = fn(&CleanupContext) -> Result<()>;
+
+/// worktree ごとの状態を持つサブシステムの後始末。
+/// 新しく状態を保存するときは、ここに消去処理を登録する。
+const CLEANUPS: &[(&str, CleanupFn)] = &[
+    ("metadata", metadata::cleanup),
+    ("empty directories", remove_empty_parents),
+];
+
+/// 登録された後始末をすべて実行する。失敗しても削除自体は成功しているので警告にとどめる
+pub fn run_all(ctx: &CleanupContext) {
+    for (name, cleanup) in CLEANUPS {
+        if let Err(err) = cleanup(ctx) {
+            warn!("cleanup of {} failed for {}: {:#}", name, ctx.worktree_path.display(), err);
+        }
+    }
+}
+
+/// `base_dir/repo/feature/auth` を削除した後に残る空の `feature/` や `repo/` を削除する
+pub fn remove_empty_parents(ctx: &CleanupContext) -> Result<()> {
+    if !ctx.worktree_path.starts_with(&ctx.base_dir) {
+        return Ok(());
+    }
+
+    let mut dir = ctx.worktree_path.parent();
+    while let Some(current) = dir {
+        if current == ctx.base_dir || !current.starts_with(&ctx.base_dir) {
+            break;
+        }
+        let is_empty = match fs::read_dir(current) {
+            Ok(mut entries) => entries.next().is_none(),
+            Err(_) => break,
+        };
+        if !is_empty {
+            break;
+        }
+        fs::remove_dir(current).with_context(|| format!("failed to remove {}", current.display()))?;
+        dir = current.parent();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn removes_empty_parents_up_to_base_dir() {
+        let temp = TempDir::new().expect("temp dir");
+        let base = temp.path().join("worktree");
+        let removed = base.join("repo/feature/auth");
+        fs::create_dir_all(&removed).unwrap();
+        fs::create_dir_all(base.join("repo/fix/other")).unwrap();
+        fs::remove_dir(&removed).unwrap();
+
+        let ctx = CleanupContext {
+            worktree_path: removed.clone(),
+            admin_dir: None,
+            base_dir: base.clone(),
+        };
+        remove_empty_parents(&ctx).unwrap();
+
+        assert!(!base.join("repo/feature").exists());
+        assert!(base.join("repo/fix/other").exists(), "non-empty siblings stay");
+
+        fs::remove_dir(base.join("repo/fix/other")).unwrap();
+        let ctx = CleanupContext {
+            worktree_path: base.join("repo/fix/other"),
+            ..ctx
+        };
+        remove_empty_parents(&ctx).unwrap();
+        assert!(!base.join("repo").exists());
+        assert!(base.exists(), "base_dir itself is kept");
+    }
+}
@@ -1,5 +1,10 @@
 use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use anyhow::Result;
+
+use crate::error::AppError;
+use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::WorktreeInfo;
 
 pub fn normalize_path(path: &Path) -> PathBuf {
@@ -7,15 +12,18 @@ pub fn normalize_path(path: &Path) -> PathBuf {
     strip_extended_prefix(canonical)
 }
 
-pub fn is_managed(info: &WorktreeInfo, base_dir: &Path) -> bool {
+/// メイン worktree か、管理ディレクトリ (`Config::managed_roots`) のいずれかの配下にあるか
+pub fn is_managed(info: &WorktreeInfo, roots: &[PathBuf]) -> bool {
     if info.is_main {
         return true;
     }
     let info_path = normalize_path(&info.path);
-    let base_dir = normalize_path(base_dir);
-    info_path.starts_with(&base_dir)
+    roots
+        .iter()
+        .any(|root| info_path.starts_with(normalize_path(root)))
 }
 
+#[allow(clippy::collapsible_if)]
 pub fn display_name(info: &WorktreeInfo, base_dir: &Path) -> String {
     if info.is_main {
         return "@".to_string();
@@ -36,6 +44,52 @@ pub fn display_name(info: &WorktreeInfo, base_dir: &Path) -> String {
         .unwrap_or_else(|| info_path.to_string_lossy().to_string())
 }
 
+/// ローカルブランチ `refs/heads/<name>` が存在するか
+pub fn branch_exists(git: &GitRunner, name: &str) -> Result<bool> {
+    let reference = format!("refs/heads/{}", name);
+    match git.run(["show-ref", "--verify", "--quiet", reference.as_str()]) {
+        Ok(_) => Ok(true),
+        Err(GitError::CommandFailed { .. }) => Ok(false),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+/// 現在の UNIX 時刻 (秒)
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// git の失敗を `AppError::Git` にする。stderr があればそれをメッセージに使う
+pub fn git_error(err: GitError) -> AppError {
+    match err {
+        GitError::CommandFailed { stderr, .. } if !stderr.trim().is_empty() => {
+            AppError::git(stderr.trim().to_string())
+        }
+        err => AppError::git(err.to_string()),
+    }
+}
+
+/// `dir` で git を実行して標準出力を返す。失敗時は stderr を `AppError::Git` にする。
+pub fn run_git_in(git: &GitRunner, dir: &Path, args: &[&str]) -> Result<String> {
+    match git.run_in(dir, args.iter().copied()) {
+        Ok(output) => Ok(output.into_stdout()),
+        Err(GitError::CommandFailed {
+            command, stderr, ..
+        }) => {
+            let message = stderr.trim();
+            if message.is_empty() {
+                Err(AppError::git(format!("{} failed without error output", command)).into())
+            } else {
+                Err(AppError::git(message.to_string()).into())
+            }
+        }
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
 fn strip_extended_prefix(path: PathBuf) -> PathBuf {
     #[cfg(windows)]
     {
@@ -125,7 +179,7 @@ mod tests {
 
         let info = make_info(worktree_path, Some("feature/auth"), false);
         let base_dir = PathBuf::from(base_dir);
-        assert!(is_managed(&info, &base_dir));
+        assert!(is_managed(&info, &[base_dir]));
     }
 
     #[test]
@@ -137,6 +191,6 @@ mod tests {
 
         let info = make_info(worktree_path, Some("feature"), false);
         let base_dir = PathBuf::from(base_dir);
-        assert!(!is_managed(&info, &base_dir));
+        assert!(!is_managed(&info, &[base_dir]));
     }
 }
@@ -1,25 +1,34 @@
-use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
 
 use anyhow::Result;
 
 use crate::cli::ToolCommand;
 use crate::config::Config;
+use crate::config::types::{CollisionPolicy, SubmoduleMode};
 use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
 use crate::hooks::executor::HookExecutor;
-use crate::worktree::common;
+use crate::worktree::parallel::{self, PrefixedWriter};
+use crate::worktree::metadata::{self, WorktreeMetadata};
+use crate::worktree::{common, lock, naming, pathmap, stash, submodule};
 
 struct AddSpec {
     path: PathBuf,
     branch: Option<String>,
     commitish: Option<String>,
     track: bool,
+    detach: bool,
+    orphan: bool,
+    sparse: Vec<String>,
+    submodules: SubmoduleMode,
+    carry: bool,
+    from_stash: Option<String>,
     display_name: String,
 }
 
@@ -40,8 +49,8 @@ pub fn ensure_worktree(
     // しかし ToolCommand から target が None の場合 (@) は作成しないので、
     // 既存解決を優先する。
 
-    // -b または --track が指定されている場合は常に新規作成を試みる
-    if cmd.branch.is_some() || cmd.track.is_some() {
+    // -b / --track / --detach / --orphan が指定されている場合は常に新規作成を試みる
+    if !cmd.branches.is_empty() || cmd.track.is_some() || cmd.detach || cmd.orphan.is_some() {
         return create_new_worktree(repo, git, config, cmd, &existing);
     }
 
@@ -75,28 +84,91 @@ fn create_new_worktree(
     cmd: &ToolCommand,
     existing: &[WorktreeInfo],
 ) -> Result<PathBuf> {
-    let spec = build_spec(repo, config, cmd, existing)?;
+    let mut stdout = io::stdout().lock();
+    let spec = add_from_spec(&mut stdout, repo, git, config, cmd, existing)?;
+
+    apply_changes(&mut stdout, repo, git, &spec)?;
+
+    let executor = HookExecutor::new(config, repo.main_root());
+    executor.execute_post_create_hooks(&mut stdout, &spec.path)?;
+
+    Ok(spec.path)
+}
+
+/// worktree を作成し、サブモジュールの初期化までを行う。
+/// `--carry` / `--from-stash` と post-create フックは実行しない。
+pub fn add_worktree<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &ToolCommand,
+) -> Result<PathBuf> {
+    let existing = list_worktrees(git)?;
+    let spec = add_from_spec(writer, repo, git, config, cmd, &existing)?;
+    Ok(spec.path)
+}
+
+fn add_from_spec<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &ToolCommand,
+    existing: &[WorktreeInfo],
+) -> Result<AddSpec> {
+    let spec = register_worktree(writer, repo, git, config, cmd, existing)?;
+    prepare_worktree(writer, repo, git, cmd, &spec)?;
+    Ok(spec)
+}
+
+/// `git worktree add` で worktree を作成する。Git の worktree 一覧を更新するのはここだけ
+fn register_worktree<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &ToolCommand,
+    existing: &[WorktreeInfo],
+) -> Result<AddSpec> {
+    let spec = build_spec(repo, git, config, cmd, existing)?;
 
     ensure_parents_exist(&spec.path)?;
     run_git_add(git, &spec)?;
 
-    let mut stdout = io::stdout().lock();
     let display_path = common::normalize_path(&spec.path);
     writeln!(
-        stdout,
+        writer,
         "Created worktree '{}' at {}",
         spec.display_name,
         display_path.display()
     )?;
+    if !spec.sparse.is_empty() {
+        writeln!(writer, "Sparse checkout (cone): {}", spec.sparse.join(", "))?;
+    }
+    Ok(spec)
+}
 
-    let executor = HookExecutor::new(config, repo.main_root());
-    executor.execute_post_create_hooks(&mut stdout, &spec.path)?;
+/// 作成した worktree のサブモジュールを初期化し、指定があればロックする
+fn prepare_worktree<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    cmd: &ToolCommand,
+    spec: &AddSpec,
+) -> Result<()> {
+    submodule::initialize(writer, git, repo.common_dir(), &spec.path, spec.submodules)?;
 
-    Ok(spec.path)
+    if let Some(reason) = &cmd.lock {
+        lock::lock_worktree(git, &spec.path, reason)?;
+        writeln!(writer, "Locked '{}'", spec.display_name)?;
+    }
+    Ok(())
 }
 
 fn build_spec(
     repo: &RepoContext,
+    git: &GitRunner,
     config: &Config,
     cmd: &ToolCommand,
     existing: &[WorktreeInfo],
@@ -104,8 +176,7 @@ fn build_spec(
     let base_dir = config.resolved_base_dir(repo.main_root());
 
     let branch_flag = cmd
-        .branch
-        .as_ref()
+        .branch()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty());
     let track_flag = cmd
@@ -119,7 +190,19 @@ fn build_spec(
         .map(|s| s.trim())
         .filter(|s| !s.is_empty());
 
-    let (branch, commitish, track) = if let Some(track) = track_flag {
+    let orphan_flag = cmd
+        .orphan
+        .as_ref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty());
+
+    let (branch, commitish, track) = if let Some(orphan) = orphan_flag {
+        // --orphan <BRANCH>: 履歴を持たないブランチなので commitish は不要
+        (Some(orphan.to_string()), None, false)
+    } else if cmd.detach {
+        // --detach [COMMITISH]: ブランチは作成せず、省略時は HEAD を切り離す
+        (None, Some(target_arg.unwrap_or("HEAD").to_string()), false)
+    } else if let Some(track) = track_flag {
         let inferred_branch = branch_flag
             .map(|s| s.to_string())
             .or_else(|| infer_branch_from_track(track));
@@ -149,13 +232,106 @@ fn build_spec(
         return Err(AppError::user("branch or target is required for creation").into());
     };
 
-    let identifier = branch
-        .clone()
-        .or_else(|| commitish.clone())
-        .ok_or_else(|| AppError::user("unable to determine worktree name"))
-        .map_err(anyhow::Error::from)?;
+    // 新しく作るブランチは命名規則を満たす必要がある (--track はリモートの名前をそのまま使う)
+    if let Some(name) = &branch
+        && !track
+    {
+        naming::validate_branch_name(git, config, name)?;
+    }
+
+    let identifier = if cmd.detach {
+        // 生の commitish (HEAD~3 や完全な SHA) ではなく、タグ名か短縮 SHA で名前を付ける
+        detached_identifier(git, commitish.as_deref().unwrap_or("HEAD"))?
+    } else {
+        branch
+            .clone()
+            .or_else(|| commitish.clone())
+            .ok_or_else(|| AppError::user("unable to determine worktree name"))
+            .map_err(anyhow::Error::from)?
+    };
+
+    let display_name = branch.clone().unwrap_or_else(|| identifier.clone());
+    let path = detect_conflicts(
+        &layout_path(repo, &base_dir, &identifier)?,
+        &display_name,
+        branch.as_deref(),
+        existing,
+        config.path_collision,
+    )?;
+
+    let sparse = resolve_sparse(config, cmd)?;
+    let submodules = if orphan_flag.is_some() {
+        SubmoduleMode::None
+    } else {
+        cmd.submodules.unwrap_or(config.submodules)
+    };
+
+    Ok(AddSpec {
+        path,
+        branch,
+        commitish,
+        track,
+        detach: cmd.detach,
+        orphan: orphan_flag.is_some(),
+        sparse,
+        submodules,
+        carry: cmd.carry,
+        from_stash: cmd
+            .from_stash
+            .as_ref()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty()),
+        display_name,
+    })
+}
+
+/// --carry / --from-stash で指定された変更を新しい worktree に持ち込む
+fn apply_changes<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    spec: &AddSpec,
+) -> Result<()> {
+    if spec.carry {
+        stash::carry(writer, git, repo.worktree_root(), &spec.path, &spec.display_name)
+    } else if let Some(name) = &spec.from_stash {
+        stash::apply(writer, git, &spec.path, name)
+    } else {
+        Ok(())
+    }
+}
 
-    let relative = branch_to_relative_path(&identifier);
+/// sparse-checkout の対象ディレクトリを決定する。
+/// 優先順位は --sparse > --profile の sparse > gwe.sparse.default。
+fn resolve_sparse(config: &Config, cmd: &ToolCommand) -> Result<Vec<String>> {
+    let profile = match &cmd.profile {
+        Some(name) => Some(config.profile(name).ok_or_else(|| {
+            AppError::config(format!(
+                "profile '{}' is not defined (set gwe.profile.{}.*)",
+                name, name
+            ))
+        })?),
+        None => None,
+    };
+
+    // orphan ブランチはチェックアウトするファイルを持たない
+    if cmd.no_sparse || cmd.orphan.is_some() {
+        return Ok(Vec::new());
+    }
+    if !cmd.sparse.is_empty() {
+        return Ok(cmd.sparse.clone());
+    }
+    if let Some(profile) = profile
+        && !profile.sparse.is_empty()
+    {
+        return Ok(profile.sparse.clone());
+    }
+    Ok(config.sparse_default.clone())
+}
+
+/// ブランチ名 (detached HEAD ではタグ名か短縮 SHA) に対応する `base_dir/<repo>/<branch>` のパス
+pub fn layout_path(repo: &RepoContext, base_dir: &Path, identifier: &str) -> Result<PathBuf> {
+    let relative = pathmap::branch_to_relative_path(identifier);
     if relative.components().next().is_none() {
         return Err(AppError::user(format!(
             "worktree name resolves to an empty path: {}",
@@ -171,20 +347,31 @@ fn build_spec(
         .and_then(|n| n.to_str())
         .unwrap_or("repo");
 
-    let final_relative = PathBuf::from(repo_name).join(&relative);
-
-    let path = base_dir.join(&final_relative);
-    detect_conflicts(&path, branch.as_deref(), existing)?;
+    Ok(base_dir.join(repo_name).join(relative))
+}
 
-    let display_name = branch.clone().unwrap_or_else(|| identifier.clone());
+/// detached worktree の名前を決定する。
+/// タグを指定された場合はタグ名、それ以外はコミットの短縮 SHA を使う。
+pub fn detached_identifier(git: &GitRunner, commitish: &str) -> Result<String> {
+    let tag = commitish.strip_prefix("refs/tags/").unwrap_or(commitish);
+    let tag_ref = format!("refs/tags/{}", tag);
+    if git
+        .run(["rev-parse", "--verify", "--quiet", tag_ref.as_str()])
+        .is_ok()
+    {
+        return Ok(tag.to_string());
+    }
 
-    Ok(AddSpec {
-        path,
-        branch,
-        commitish,
-        track,
-        display_name,
-    })
+    let revision = format!("{}^{{commit}}", commitish);
+    match git.run(["rev-parse", "--verify", "--quiet", "--short", revision.as_str()]) {
+        Ok(output) => Ok(output.stdout().trim().to_string()),
+        Err(GitError::CommandFailed { .. }) => Err(AppError::git(format!(
+            "invalid reference: {}",
+            commitish
+        ))
+        .into()),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
 }
 
 fn infer_branch_from_track(track: &str) -> Option<String> {
@@ -194,73 +381,67 @@ fn infer_branch_from_track(track: &str) -> Option<String> {
         .filter(|branch| !branch.is_empty())
 }
 
-fn branch_to_relative_path(name: &str) -> PathBuf {
-    let mut result = PathBuf::new();
-    for segment in name.split(['/', '\\']) {
-        let sanitized = sanitize_segment(segment);
-        if !sanitized.is_empty() {
-            result.push(sanitized);
-        }
-    }
-    if result.as_os_str().is_empty() {
-        result.push(sanitize_segment(name));
-    }
-    result
-}
-
-fn sanitize_segment(segment: &str) -> String {
-    if segment.is_empty() || segment == "." || segment == ".." {
-        return "_".to_string();
-    }
-
-    let invalid_chars: HashSet<char> = ['<', '>', ':', '"', '|', '?', '*', '\\']
-        .into_iter()
-        .collect();
-
-    segment
-        .chars()
-        .map(|ch| if invalid_chars.contains(&ch) { '_' } else { ch })
-        .collect()
-}
-
-fn detect_conflicts(path: &Path, branch: Option<&str>, existing: &[WorktreeInfo]) -> Result<()> {
-    if let Some(branch_name) = branch {
-        if let Some(conflict) = existing
+/// 作成先パスの衝突を検査し、実際に使うパスを返す。
+/// 別ブランチが大文字小文字や Unicode 正規化の違いだけで同じパスになる場合は、
+/// `gwe.worktrees.onCollision` に従ってエラーにするか連番を付ける。
+fn detect_conflicts(
+    path: &Path,
+    name: &str,
+    branch: Option<&str>,
+    existing: &[WorktreeInfo],
+    policy: CollisionPolicy,
+) -> Result<PathBuf> {
+    if let Some(branch_name) = branch
+        && let Some(conflict) = existing
             .iter()
             .find(|wt| wt.branch.as_deref() == Some(branch_name))
-        {
-            return Err(AppError::user(format!(
-                "worktree for branch '{}' already exists: {}",
-                branch_name,
-                conflict.path.display()
-            ))
-            .into());
-        }
-    }
-
-    let target_normalized = common::normalize_path(path);
-
-    if existing
-        .iter()
-        .map(|wt| common::normalize_path(&wt.path))
-        .any(|existing_path| existing_path == target_normalized)
     {
         return Err(AppError::user(format!(
-            "worktree path already exists in git metadata: {}",
-            path.display()
+            "worktree for branch '{}' already exists: {}",
+            branch_name,
+            conflict.path.display()
         ))
         .into());
     }
 
-    if path.exists() {
+    let mut candidate = path.to_path_buf();
+    let mut attempt = 1;
+    while let Some(other) = find_collision(&candidate, existing) {
+        if policy == CollisionPolicy::Error {
+            let other_name = other
+                .branch
+                .clone()
+                .unwrap_or_else(|| other.path.display().to_string());
+            return Err(AppError::user(format!(
+                "branches '{}' and '{}' map to the same worktree path: {}\n\
+                 Rename one of them or set gwe.worktrees.onCollision=suffix",
+                name,
+                other_name,
+                candidate.display()
+            ))
+            .into());
+        }
+        attempt += 1;
+        candidate = pathmap::with_suffix(path, attempt);
+    }
+
+    if candidate.exists() {
         return Err(AppError::user(format!(
             "destination path already exists: {}",
-            path.display()
+            candidate.display()
         ))
         .into());
     }
 
-    Ok(())
+    Ok(candidate)
+}
+
+/// 大文字小文字・Unicode 正規化の違いを無視して同じパスになる既存 worktree を探す
+fn find_collision<'a>(path: &Path, existing: &'a [WorktreeInfo]) -> Option<&'a WorktreeInfo> {
+    let key = pathmap::collision_key(&common::normalize_path(path));
+    existing
+        .iter()
+        .find(|wt| pathmap::collision_key(&common::normalize_path(&wt.path)) == key)
 }
 
 fn ensure_parents_exist(path: &Path) -> Result<()> {
@@ -270,59 +451,215 @@ fn ensure_parents_exist(path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// ブランチ名にインデックスを付与した名前を生成
-fn generate_branch_name(base: &str, index: u8) -> String {
-    format!("{}-{}", base, index)
+/// `base` に `gwe.multi.suffix` の書式で連番を付けたブランチ名を生成する。
+/// `{n}` は連番、`{0n}` は作成数の桁数にゼロ埋めした連番に置き換える。
+pub fn sibling_branch_names(config: &Config, base: &str, count: u8) -> Vec<String> {
+    let width = count.to_string().len();
+    (1..=count)
+        .map(|index| {
+            let suffix = config
+                .multi_suffix
+                .replace("{0n}", &format!("{:0width$}", index, width = width))
+                .replace("{n}", &index.to_string());
+            format!("{}{}", base, suffix)
+        })
+        .collect()
 }
 
-/// 複数の worktree を作成し、パスのリストを返す
+/// 複数の worktree を作成し、パスのリストを返す。
+/// `template` の作成オプション (--track や --sparse など) は各 worktree に引き継がれる。
+/// `git worktree add` は 1 つずつ順番に実行し、フックは最大 `--jobs` 個まで並列に実行する。
+/// 作成した worktree は 1 つのグループとして記録し、`tools` があれば兄弟ごとのツールも記録する。
 pub fn create_multiple_worktrees(
     repo: &RepoContext,
     git: &GitRunner,
     config: &Config,
-    base_branch: &str,
-    count: u8,
-    track: Option<&str>,
+    branches: &[String],
+    tools: &[String],
+    template: &ToolCommand,
 ) -> Result<Vec<PathBuf>> {
+    if template.carry {
+        return Err(AppError::user("--carry cannot be used when creating multiple worktrees").into());
+    }
+
     let existing = list_worktrees(git)?;
-    let mut paths = Vec::with_capacity(count as usize);
+    let group = template
+        .group
+        .clone()
+        .unwrap_or_else(|| metadata::default_group_name(branches));
+    if !metadata::group_members(&existing, &group).is_empty() {
+        return Err(AppError::user(format!(
+            "group '{}' already exists; choose another name with --group",
+            group
+        ))
+        .into());
+    }
+
+    let count = branches.len();
+    let jobs = template.jobs.map(usize::from).unwrap_or_else(default_jobs);
+    let sink = Mutex::new(io::stdout());
+    let known = Mutex::new(existing);
+
+    let outcomes = parallel::run_limited(branches, jobs, |index, branch| {
+        let mut writer = PrefixedWriter::new(format!("[{}/{}] ", index + 1, count), &sink);
+        let mut path = None;
+        let result = create_sibling(
+            &mut writer,
+            &mut path,
+            SiblingContext {
+                repo,
+                git,
+                config,
+                template,
+                known: &known,
+            },
+            branch,
+            WorktreeMetadata {
+                group: Some(group.clone()),
+                index: Some(index + 1),
+                tool: tools.get(index).cloned(),
+                ..WorktreeMetadata::default()
+            },
+        );
+        if let Err(err) = &result {
+            let _ = writeln!(writer, "✗ {}", err);
+        }
+        SiblingOutcome {
+            branch: branch.clone(),
+            path,
+            error: result.err().map(|err| err.to_string()),
+        }
+    });
+
+    let mut stdout = io::stdout().lock();
+    write_sibling_summary(&mut stdout, &outcomes)?;
+    writeln!(stdout, "Group: {}", group)?;
+
+    let failed = outcomes.iter().filter(|o| o.error.is_some()).count();
+    if failed > 0 {
+        return Err(AppError::user(format!(
+            "{} of {} worktrees could not be created",
+            failed, count
+        ))
+        .into());
+    }
 
-    for i in 1..=count {
-        let branch_name = generate_branch_name(base_branch, i);
+    Ok(outcomes.into_iter().filter_map(|o| o.path).collect())
+}
 
-        let cmd = crate::cli::ToolCommand {
-            target: None,
-            branch: Some(branch_name.clone()),
-            track: track.map(|s| s.to_string()),
-            multiplier: None,
-            args: vec![],
-        };
+struct SiblingContext<'a> {
+    repo: &'a RepoContext,
+    git: &'a GitRunner,
+    config: &'a Config,
+    template: &'a ToolCommand,
+    /// 既存の worktree と作成済みの兄弟。`git worktree add` の排他制御も兼ねる
+    known: &'a Mutex<Vec<WorktreeInfo>>,
+}
 
-        let spec = build_spec(repo, config, &cmd, &existing)?;
-        ensure_parents_exist(&spec.path)?;
-        run_git_add(git, &spec)?;
+struct SiblingOutcome {
+    branch: String,
+    path: Option<PathBuf>,
+    error: Option<String>,
+}
 
-        let mut stdout = io::stdout().lock();
-        let display_path = common::normalize_path(&spec.path);
-        writeln!(
-            stdout,
-            "[{}/{}] Created worktree '{}' at {}",
-            i,
-            count,
-            spec.display_name,
-            display_path.display()
-        )?;
+fn create_sibling<W: Write>(
+    writer: &mut W,
+    path: &mut Option<PathBuf>,
+    ctx: SiblingContext<'_>,
+    branch: &str,
+    sibling: WorktreeMetadata,
+) -> Result<()> {
+    let cmd = ToolCommand {
+        target: None,
+        branches: vec![branch.to_string()],
+        multiplier: None,
+        ..ctx.template.clone()
+    };
+
+    // Git の worktree 一覧を更新する `git worktree add` は同時に実行しない。
+    // 作成したパスは一覧に加え、後に続く兄弟同士の衝突も検出できるようにする
+    let spec = {
+        let mut known = ctx.known.lock().unwrap_or_else(PoisonError::into_inner);
+        let spec = register_worktree(writer, ctx.repo, ctx.git, ctx.config, &cmd, &known)?;
+        known.push(WorktreeInfo {
+            path: spec.path.clone(),
+            head: String::new(),
+            branch: spec.branch.clone(),
+            is_main: false,
+            is_detached: spec.branch.is_none(),
+            locked: None,
+            prunable: None,
+        });
+        spec
+    };
+    *path = Some(spec.path.clone());
+    metadata::save(&spec.path, &sibling)?;
 
-        let executor = HookExecutor::new(config, repo.main_root());
-        executor.execute_post_create_hooks(&mut stdout, &spec.path)?;
+    prepare_worktree(writer, ctx.repo, ctx.git, &cmd, &spec)?;
+    apply_changes(writer, ctx.repo, ctx.git, &spec)?;
+
+    let executor = HookExecutor::new(ctx.config, ctx.repo.main_root());
+    executor.execute_post_create_hooks(writer, &spec.path)?;
+    Ok(())
+}
+
+fn write_sibling_summary<W: Write>(writer: &mut W, outcomes: &[SiblingOutcome]) -> Result<()> {
+    let count = outcomes.len();
+    let rows: Vec<[String; 4]> = outcomes
+        .iter()
+        .enumerate()
+        .map(|(index, outcome)| {
+            let (status, detail) = match (&outcome.error, &outcome.path) {
+                (None, Some(path)) => ("ok", common::normalize_path(path).display().to_string()),
+                (Some(err), _) => ("failed", err.lines().next().unwrap_or_default().to_string()),
+                (None, None) => ("failed", String::new()),
+            };
+            [
+                format!("{}/{}", index + 1, count),
+                outcome.branch.clone(),
+                status.to_string(),
+                detail,
+            ]
+        })
+        .collect();
 
-        paths.push(spec.path);
+    let headers = ["#", "BRANCH", "RESULT", "DETAIL"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    writeln!(writer)?;
+    let header: Vec<String> = headers
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+        .collect();
+    writeln!(writer, "{}", header.join("  ").trim_end())?;
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        writeln!(writer, "{}", line.join("  ").trim_end())?;
     }
+    Ok(())
+}
 
-    Ok(paths)
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
 }
 
 fn run_git_add(git: &GitRunner, spec: &AddSpec) -> Result<()> {
+    if spec.orphan {
+        return run_git_add_orphan(git, spec);
+    }
+
     let mut args: Vec<OsString> = Vec::new();
     args.push("worktree".into());
     args.push("add".into());
@@ -331,6 +668,14 @@ fn run_git_add(git: &GitRunner, spec: &AddSpec) -> Result<()> {
         args.push("--track".into());
     }
 
+    if spec.detach {
+        args.push("--detach".into());
+    }
+
+    if !spec.sparse.is_empty() {
+        args.push("--no-checkout".into());
+    }
+
     if let Some(branch) = &spec.branch {
         args.push("-b".into());
         args.push(branch.clone().into());
@@ -342,16 +687,80 @@ fn run_git_add(git: &GitRunner, spec: &AddSpec) -> Result<()> {
         args.push(commitish.clone().into());
     }
 
-    match git.run(args) {
-        Ok(_) => Ok(()),
-        Err(GitError::CommandFailed { stderr, .. }) => {
+    git.run(args).map_err(worktree_add_error)?;
+
+    if !spec.sparse.is_empty()
+        && let Err(err) = apply_sparse_checkout(git, spec)
+    {
+        // 中途半端な worktree と、このコマンドで作ったブランチを残さない
+        let path = spec.path.to_string_lossy().into_owned();
+        let _ = git.run(["worktree", "remove", "--force", path.as_str()]);
+        if let Some(branch) = &spec.branch {
+            let _ = git.run(["branch", "-D", branch.as_str()]);
+        }
+        return Err(err);
+    }
+
+    Ok(())
+}
+
+/// `--no-checkout` で作成した worktree に cone モードの sparse-checkout を設定してからチェックアウトする
+fn apply_sparse_checkout(git: &GitRunner, spec: &AddSpec) -> Result<()> {
+    let mut args: Vec<OsString> = vec!["sparse-checkout".into(), "set".into(), "--cone".into()];
+    args.extend(spec.sparse.iter().map(OsString::from));
+
+    git.run_in(&spec.path, args).map_err(worktree_add_error)?;
+    git.run_in(&spec.path, ["checkout"])
+        .map_err(worktree_add_error)?;
+    Ok(())
+}
+
+/// `git worktree add --orphan` は Git 2.42 以降にしか存在しないため、
+/// 空の detached worktree を作成してから orphan ブランチへ切り替える。
+fn run_git_add_orphan(git: &GitRunner, spec: &AddSpec) -> Result<()> {
+    let branch = spec
+        .branch
+        .as_deref()
+        .ok_or_else(|| AppError::user("--orphan requires a branch name"))?;
+
+    let branch_ref = format!("refs/heads/{}", branch);
+    if git
+        .run(["rev-parse", "--verify", "--quiet", branch_ref.as_str()])
+        .is_ok()
+    {
+        return Err(AppError::user(format!("branch '{}' already exists", branch)).into());
+    }
+
+    let path = spec.path.to_string_lossy().into_owned();
+    git.run([
+        "worktree",
+        "add",
+        "--no-checkout",
+        "--detach",
+        path.as_str(),
+        "HEAD",
+    ])
+    .map_err(worktree_add_error)?;
+
+    if let Err(err) = git.run_in(&spec.path, ["switch", "--orphan", branch]) {
+        // 中途半端な worktree を残さない
+        let _ = git.run(["worktree", "remove", "--force", path.as_str()]);
+        return Err(worktree_add_error(err).into());
+    }
+
+    Ok(())
+}
+
+fn worktree_add_error(err: GitError) -> AppError {
+    match err {
+        GitError::CommandFailed { stderr, .. } => {
             let message = stderr.trim();
             if message.is_empty() {
-                Err(AppError::git("git worktree add failed without error output").into())
+                AppError::git("git worktree add failed without error output")
             } else {
-                Err(AppError::git(message.to_string()).into())
+                AppError::git(message.to_string())
             }
         }
-        Err(err) => Err(AppError::git(err.to_string()).into()),
+        err => AppError::git(err.to_string()),
     }
 }
@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+
+use crate::cli::{DoctorCommand, OrphanAction};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::cleanup::{self, CleanupContext};
+use crate::worktree::process::ProcessTable;
+use crate::worktree::{common, metadata, naming};
+
+/// これより新しい index.lock は実行中の git のものとみなす
+const STALE_LOCK_AGE: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// `--fix` で行う修復
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fix {
+    /// `git worktree repair <path>`
+    Repair(PathBuf),
+    /// `git worktree prune`
+    Prune,
+    /// 残った lock ファイルを削除
+    RemoveLock(PathBuf),
+    /// git が知らないディレクトリを取り込むか削除する
+    Orphan(PathBuf),
+    /// 空のディレクトリを削除
+    RemoveEmpty(PathBuf),
+    /// 自動では直せないので手順だけを示す
+    Manual(String),
+}
+
+impl Fix {
+    fn describe(&self) -> String {
+        match self {
+            Fix::Repair(path) => format!("git worktree repair {}", path.display()),
+            Fix::Prune => "git worktree prune".to_string(),
+            Fix::RemoveLock(_) => "remove the lock file".to_string(),
+            Fix::Orphan(_) => "import it as a worktree or delete it".to_string(),
+            Fix::RemoveEmpty(_) => "remove the empty directory".to_string(),
+            Fix::Manual(hint) => hint.clone(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Finding {
+    severity: Severity,
+    message: String,
+    fix: Fix,
+}
+
+/// worktree の管理情報を診断し、`--fix` なら修復する
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &DoctorCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let common_dir = if repo.common_dir().is_absolute() {
+        common::normalize_path(repo.common_dir())
+    } else {
+        common::normalize_path(&repo.worktree_root().join(repo.common_dir()))
+    };
+    let admins = admin_dirs(&common_dir);
+    let base_dir = config.resolved_base_dir(repo.main_root());
+
+    let mut findings = Vec::new();
+    let moved = check_layout(repo, &worktrees, &admins, &base_dir, &mut findings);
+    check_links(&worktrees, &admins, &mut findings);
+    check_prunable(&worktrees, &admins, &moved, &mut findings);
+    check_locks(&worktrees, &common_dir, &admins, &mut findings);
+    check_shell_integration(&mut findings);
+    findings.sort_by_key(|finding| finding.severity);
+
+    let mut stdout = io::stdout().lock();
+    if findings.is_empty() {
+        writeln!(stdout, "No problems found")?;
+        return Ok(());
+    }
+    for finding in &findings {
+        writeln!(stdout, "{:<8} {}", finding.severity.label(), finding.message)?;
+        writeln!(stdout, "{:<8} fix: {}", "", finding.fix.describe())?;
+    }
+    let errors = findings
+        .iter()
+        .filter(|finding| finding.severity == Severity::Error)
+        .count();
+    writeln!(
+        stdout,
+        "{} error(s), {} warning(s)",
+        errors,
+        findings.len() - errors
+    )?;
+
+    if !cmd.fix {
+        if errors > 0 {
+            return Err(AppError::user(format!(
+                "{} problem(s) need repair; run 'gwe doctor --fix'",
+                errors
+            ))
+            .into());
+        }
+        return Ok(());
+    }
+
+    writeln!(stdout)?;
+    apply_fixes(&mut stdout, git, config, &base_dir, &findings, cmd)
+}
+
+fn apply_fixes<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    config: &Config,
+    base_dir: &Path,
+    findings: &[Finding],
+    cmd: &DoctorCommand,
+) -> Result<()> {
+    let main_root = git.repo().main_root();
+
+    // 移動された worktree を先に繋ぎ直す。prune を先に行うと管理情報が消えてしまう
+    let repairs: Vec<String> = findings
+        .iter()
+        .filter_map(|finding| match &finding.fix {
+            Fix::Repair(path) => Some(path.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    if !repairs.is_empty() {
+        let mut args = vec!["worktree".to_string(), "repair".to_string()];
+        args.extend(repairs.iter().cloned());
+        git.run_in(main_root, args).map_err(common::git_error)?;
+        writeln!(writer, "Repaired {} worktree link(s)", repairs.len())?;
+    }
+
+    if findings.iter().any(|finding| finding.fix == Fix::Prune) {
+        git.run_in(main_root, ["worktree", "prune"]).map_err(common::git_error)?;
+        writeln!(writer, "Pruned stale worktree entries")?;
+    }
+
+    for finding in findings {
+        match &finding.fix {
+            Fix::RemoveLock(lock) => {
+                fs::remove_file(lock)
+                    .with_context(|| format!("failed to remove {}", lock.display()))?;
+                writeln!(writer, "Removed {}", lock.display())?;
+            }
+            Fix::RemoveEmpty(path) => {
+                let ctx = CleanupContext::capture(path, base_dir);
+                fs::remove_dir_all(path)
+                    .with_context(|| format!("failed to remove {}", path.display()))?;
+                cleanup::run_all(&ctx);
+                writeln!(writer, "Removed {}", path.display())?;
+            }
+            Fix::Orphan(path) => {
+                let action = match cmd.orphans {
+                    Some(action) => action,
+                    None => ask_orphan(path)?,
+                };
+                match action {
+                    OrphanAction::Import => import_orphan(writer, git, config, base_dir, path)?,
+                    OrphanAction::Delete => {
+                        ensure_not_repository(path)?;
+                        let ctx = CleanupContext::capture(path, base_dir);
+                        fs::remove_dir_all(path)
+                            .with_context(|| format!("failed to delete {}", path.display()))?;
+                        cleanup::run_all(&ctx);
+                        writeln!(writer, "Deleted {}", path.display())?;
+                    }
+                    OrphanAction::Keep => writeln!(writer, "Kept {}", path.display())?,
+                }
+            }
+            Fix::Repair(_) | Fix::Prune | Fix::Manual(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// `<common-dir>/worktrees/<id>` と、その `gitdir` が指す worktree のパス
+fn admin_dirs(common_dir: &Path) -> HashMap<PathBuf, PathBuf> {
+    let Ok(entries) = fs::read_dir(common_dir.join("worktrees")) else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let gitdir = fs::read_to_string(entry.path().join("gitdir")).ok()?;
+            let worktree = Path::new(gitdir.trim()).parent()?.to_path_buf();
+            Some((
+                common::normalize_path(&entry.path()),
+                common::normalize_path(&worktree),
+            ))
+        })
+        .collect()
+}
+
+/// `base_dir/<repo>` 配下で、git が知らないディレクトリと手で移動された worktree を探す。
+/// 移動された worktree の管理ディレクトリを返す。
+fn check_layout(
+    repo: &RepoContext,
+    worktrees: &[WorktreeInfo],
+    admins: &HashMap<PathBuf, PathBuf>,
+    base_dir: &Path,
+    findings: &mut Vec<Finding>,
+) -> Vec<PathBuf> {
+    // ディレクトリが既にない worktree は prunable として扱い、その親は空なら削除対象にする
+    let registered: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .filter(|path| path.is_dir())
+        .collect();
+    let root = common::normalize_path(&base_dir.join(repo.repo_name()));
+    let mut moved = Vec::new();
+    scan_dir(&root, &registered, admins, findings, &mut moved);
+    moved
+}
+
+fn scan_dir(
+    dir: &Path,
+    registered: &[PathBuf],
+    admins: &HashMap<PathBuf, PathBuf>,
+    findings: &mut Vec<Finding>,
+    moved: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| common::normalize_path(&entry.path()))
+        .collect();
+    dirs.sort();
+
+    for path in dirs {
+        if registered.contains(&path) {
+            continue;
+        }
+        if is_repository(&path) {
+            findings.push(Finding {
+                severity: Severity::Warning,
+                message: format!("{} is a separate git repository, not a worktree", path.display()),
+                fix: Fix::Manual("move it out of the worktree directory".to_string()),
+            });
+        } else if path.join(".git").exists() {
+            let admin = metadata::admin_dir(&path)
+                .map(|admin| common::normalize_path(&admin))
+                .filter(|admin| admins.contains_key(admin));
+            if let Some(admin) = admin {
+                findings.push(Finding {
+                    severity: Severity::Error,
+                    message: format!(
+                        "worktree at {} was moved without git; git still expects it at {}",
+                        path.display(),
+                        admins[&admin].display()
+                    ),
+                    fix: Fix::Repair(path),
+                });
+                moved.push(admin);
+            } else {
+                findings.push(orphan(path));
+            }
+        } else if registered.iter().any(|known| known.starts_with(&path)) || contains_git(&path) {
+            scan_dir(&path, registered, admins, findings, moved);
+        } else {
+            findings.push(orphan(path));
+        }
+    }
+}
+
+fn orphan(path: PathBuf) -> Finding {
+    if is_empty_tree(&path) {
+        return Finding {
+            severity: Severity::Warning,
+            message: format!("{} is an empty directory", path.display()),
+            fix: Fix::RemoveEmpty(path),
+        };
+    }
+    Finding {
+        severity: Severity::Warning,
+        message: format!("{} is not a registered worktree", path.display()),
+        fix: Fix::Orphan(path),
+    }
+}
+
+/// `.git` ディレクトリを持つ独立したリポジトリ (clone など) か。
+/// worktree の `.git` はファイルなので、これを取り込んだり削除したりすると履歴が失われる
+fn is_repository(dir: &Path) -> bool {
+    dir.join(".git").is_dir()
+}
+
+/// 配下に `.git` を持つディレクトリ (移動された worktree など) があるか
+fn contains_git(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            entry.file_type().is_ok_and(|kind| kind.is_dir())
+                && (entry.path().join(".git").exists() || contains_git(&entry.path()))
+        })
+    })
+}
+
+/// 空のディレクトリしか含まないか
+fn is_empty_tree(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().all(|entry| {
+            entry.file_type().is_ok_and(|kind| kind.is_dir()) && is_empty_tree(&entry.path())
+        })
+    })
+}
+
+/// 登録済み worktree の `.git` ファイルが管理ディレクトリを正しく指しているか
+fn check_links(
+    worktrees: &[WorktreeInfo],
+    admins: &HashMap<PathBuf, PathBuf>,
+    findings: &mut Vec<Finding>,
+) {
+    for info in worktrees.iter().filter(|info| !info.is_main) {
+        let path = common::normalize_path(&info.path);
+        if !path.is_dir() {
+            continue;
+        }
+        let Some(expected) = admins
+            .iter()
+            .find(|(_, worktree)| **worktree == path)
+            .map(|(admin, _)| admin)
+        else {
+            continue;
+        };
+        let problem = match metadata::admin_dir(&path) {
+            None => Some("its .git file is missing".to_string()),
+            Some(actual) if common::normalize_path(&actual) != *expected => {
+                Some(format!("its .git file points to {}", actual.display()))
+            }
+            Some(_) => None,
+        };
+        if let Some(problem) = problem {
+            findings.push(Finding {
+                severity: Severity::Error,
+                message: format!("broken gitdir link in {}: {}", path.display(), problem),
+                fix: Fix::Repair(path),
+            });
+        }
+    }
+}
+
+fn check_prunable(
+    worktrees: &[WorktreeInfo],
+    admins: &HashMap<PathBuf, PathBuf>,
+    moved: &[PathBuf],
+    findings: &mut Vec<Finding>,
+) {
+    for info in worktrees {
+        let Some(reason) = &info.prunable else {
+            continue;
+        };
+        let path = common::normalize_path(&info.path);
+        // 移動先が見つかったものは repair で直るので prune しない
+        let is_moved = admins
+            .iter()
+            .any(|(admin, worktree)| *worktree == path && moved.contains(admin));
+        if is_moved {
+            continue;
+        }
+        findings.push(Finding {
+            severity: Severity::Warning,
+            message: format!("worktree {} is prunable: {}", path.display(), reason),
+            fix: Fix::Prune,
+        });
+    }
+}
+
+/// 強制終了した git が残した index.lock を探す
+fn check_locks(
+    worktrees: &[WorktreeInfo],
+    common_dir: &Path,
+    admins: &HashMap<PathBuf, PathBuf>,
+    findings: &mut Vec<Finding>,
+) {
+    let table = ProcessTable::scan();
+    for info in worktrees {
+        let path = common::normalize_path(&info.path);
+        if !path.is_dir() {
+            continue;
+        }
+        let git_dir = if info.is_main {
+            Some(common_dir.to_path_buf())
+        } else {
+            admins
+                .iter()
+                .find(|(_, worktree)| **worktree == path)
+                .map(|(admin, _)| admin.clone())
+        };
+        let Some(lock) = git_dir.map(|dir| dir.join("index.lock")) else {
+            continue;
+        };
+        let Ok(modified) = fs::metadata(&lock).and_then(|meta| meta.modified()) else {
+            continue;
+        };
+        let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+        let in_use = !table.inside(&lock, &[]).is_empty()
+            || table.inside(&path, &[]).iter().any(|process| {
+                process
+                    .command
+                    .split_whitespace()
+                    .next()
+                    .and_then(|program| Path::new(program).file_name())
+                    .is_some_and(|name| name == "git")
+            });
+        if age < STALE_LOCK_AGE || in_use {
+            continue;
+        }
+        findings.push(Finding {
+            severity: Severity::Error,
+            message: format!(
+                "stale index.lock in {} ({} minutes old): {}",
+                path.display(),
+                age.as_secs() / 60,
+                lock.display()
+            ),
+            fix: Fix::RemoveLock(lock),
+        });
+    }
+}
+
+/// シェル統合のラッパー関数は `GWE_SHELL_INTEGRATION` を設定する
+fn check_shell_integration(findings: &mut Vec<Finding>) {
+    if std::env::var_os("GWE_SHELL_INTEGRATION").is_some() {
+        return;
+    }
+    let shell = std::env::var("SHELL")
+        .ok()
+        .and_then(|shell| {
+            Path::new(&shell)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+        .filter(|name| name == "bash" || name == "zsh")
+        .unwrap_or_else(|| "pwsh".to_string());
+    findings.push(Finding {
+        severity: Severity::Warning,
+        message: "shell integration is not loaded, so 'gwe cd' and 'gwe rm --here' cannot change directory"
+            .to_string(),
+        fix: Fix::Manual(format!("run 'gwe init --shell {}' and restart the shell", shell)),
+    });
+}
+
+/// 端末で管理外ディレクトリの扱いを尋ねる。端末でなければ残す
+fn ask_orphan(path: &Path) -> Result<OrphanAction> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Ok(OrphanAction::Keep);
+    }
+
+    let mut stderr = io::stderr().lock();
+    write!(
+        stderr,
+        "{} is not a registered worktree. [i]mport, [d]elete or [k]eep? [k] ",
+        path.display()
+    )?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    Ok(match answer.trim().to_ascii_lowercase().as_str() {
+        "i" | "import" => OrphanAction::Import,
+        "d" | "delete" => OrphanAction::Delete,
+        _ => OrphanAction::Keep,
+    })
+}
+
+/// git が知らないディレクトリを、`base_dir/<repo>` からの相対パスをブランチ名として worktree にする。
+/// ファイルはそのまま残し、ブランチとの差分は未コミットの変更になる。
+fn import_orphan<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    config: &Config,
+    base_dir: &Path,
+    path: &Path,
+) -> Result<()> {
+    ensure_not_repository(path)?;
+    let main_root = git.repo().main_root();
+    let root = common::normalize_path(&base_dir.join(git.repo().repo_name()));
+    let branch = path
+        .strip_prefix(&root)
+        .map(|relative| {
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default();
+    if branch.is_empty()
+        || git
+            .run_in(main_root, ["check-ref-format", "--branch", branch.as_str()])
+            .is_err()
+    {
+        return Err(AppError::user(format!(
+            "cannot import {}: '{}' is not a valid branch name",
+            path.display(),
+            branch
+        ))
+        .into());
+    }
+
+    let branch_ref = format!("refs/heads/{}", branch);
+    let exists = git
+        .run_in(main_root, ["rev-parse", "--verify", "--quiet", branch_ref.as_str()])
+        .is_ok();
+    // 新しく作るブランチは `gwe add` と同じ命名規則を満たす必要がある
+    if !exists {
+        naming::validate_branch_name(git, config, &branch)?;
+    }
+    let checked_out = list_worktrees(git)?
+        .iter()
+        .any(|info| info.branch.as_deref() == Some(branch.as_str()));
+
+    let path_arg = path.to_string_lossy().into_owned();
+    let mut args = vec!["worktree", "add", "--no-checkout"];
+    match (exists, checked_out) {
+        (true, false) => args.extend([path_arg.as_str(), branch.as_str()]),
+        (true, true) => args.extend(["--detach", path_arg.as_str(), branch.as_str()]),
+        (false, _) => args.extend(["-b", branch.as_str(), path_arg.as_str()]),
+    }
+
+    // 空でないディレクトリには worktree を作れないため、一旦退避してから中身を戻す
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let aside = path.with_file_name(format!("{}.gwe-import", file_name));
+    fs::rename(path, &aside)
+        .with_context(|| format!("failed to move {} aside", path.display()))?;
+    if let Err(err) = git.run_in(main_root, args) {
+        fs::rename(&aside, path)
+            .with_context(|| format!("failed to move {} back", aside.display()))?;
+        return Err(common::git_error(err).into());
+    }
+
+    for entry in fs::read_dir(&aside)
+        .with_context(|| format!("failed to read {}", aside.display()))?
+        .flatten()
+    {
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let dest = path.join(entry.file_name());
+        fs::rename(entry.path(), &dest)
+            .with_context(|| format!("failed to move {} into the worktree", dest.display()))?;
+    }
+    fs::remove_dir_all(&aside)
+        .with_context(|| format!("failed to remove {}", aside.display()))?;
+    // index をブランチに合わせ、手元にないファイルだけをチェックアウトする
+    common::run_git_in(git, path, &["reset", "--quiet"])?;
+    common::run_git_in(git, path, &["checkout-index", "--all", "--quiet"])?;
+
+    writeln!(writer, "Imported {} as '{}'", path.display(), branch)?;
+    Ok(())
+}
+
+/// 独立したリポジトリは取り込みも削除もしない
+fn ensure_not_repository(path: &Path) -> Result<()> {
+    if is_repository(path) {
+        return Err(repository_error(path).into());
+    }
+    Ok(())
+}
+
+fn repository_error(path: &Path) -> AppError {
+    AppError::user(format!(
+        "refusing to touch {}: it is a separate git repository, not a worktree",
+        path.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn classifies_leftover_directories() {
+        let temp = TempDir::new().expect("temp dir");
+        let empty = temp.path().join("empty/nested");
+        fs::create_dir_all(&empty).unwrap();
+        let moved = temp.path().join("feature/moved");
+        fs::create_dir_all(&moved).unwrap();
+        fs::write(moved.join(".git"), "gitdir: /elsewhere\n").unwrap();
+
+        assert!(is_empty_tree(&temp.path().join("empty")));
+        assert!(!is_empty_tree(&temp.path().join("feature")));
+        assert!(contains_git(&temp.path().join("feature")));
+        assert!(!contains_git(&temp.path().join("empty")));
+        assert_eq!(
+            orphan(temp.path().join("empty")).fix,
+            Fix::RemoveEmpty(temp.path().join("empty"))
+        );
+    }
+}
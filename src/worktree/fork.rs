@@ -0,0 +1,193 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::cli::{ForkCommand, ToolCommand};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::list_worktrees;
+use crate::worktree::transfer::{self, TransferMode};
+use crate::worktree::{common, create, resolve};
+
+/// 既存 worktree の HEAD から新しいブランチと worktree を作成し、
+/// 未コミット変更と ignore されたローカルファイルを複製する。
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &ForkCommand) -> Result<()> {
+    let source = resolve::resolve_worktree_path(repo, git, config, Some(cmd.source.clone()))?;
+    let head = common::run_git_in(git, &source, &["rev-parse", "--verify", "HEAD"])
+        .map_err(|_| {
+            AppError::user(format!("worktree {} has no commits to fork from", source.display()))
+        })?;
+
+    // 複製元の配下にある他の worktree (と worktree 置き場) は複製対象から除外する
+    let mut excluded: Vec<PathBuf> = list_worktrees(git)?
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
+    excluded.push(common::normalize_path(&config.resolved_base_dir(repo.main_root())));
+    excluded.retain(|path| *path != source && path.starts_with(&source));
+
+    let tool_cmd = ToolCommand {
+        target: Some(head.trim().to_string()),
+        branches: vec![cmd.branch.clone()],
+        ..Default::default()
+    };
+
+    let mut stdout = io::stdout().lock();
+    let path = create::add_worktree(&mut stdout, repo, git, config, &tool_cmd)?;
+
+    if let Err(err) = replicate(&mut stdout, git, repo, &source, &path, &excluded, cmd) {
+        // 中途半端な状態の worktree を残さない
+        let path_arg = path.to_string_lossy().into_owned();
+        let _ = git.run(["worktree", "remove", "--force", path_arg.as_str()]);
+        let _ = git.run(["branch", "-D", cmd.branch.as_str()]);
+        return Err(err);
+    }
+
+    writeln!(
+        stdout,
+        "✓ Forked {} into '{}'",
+        source.display(),
+        cmd.branch
+    )?;
+    Ok(())
+}
+
+fn replicate<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    repo: &RepoContext,
+    source: &Path,
+    target: &Path,
+    excluded: &[PathBuf],
+    cmd: &ForkCommand,
+) -> Result<()> {
+    let summary =
+        transfer::transfer_changes(git, repo.common_dir(), source, target, &[], TransferMode::Copy)?;
+    if !summary.is_empty() {
+        writeln!(
+            writer,
+            "Copied {} uncommitted file(s)",
+            summary.tracked.len() + summary.untracked.len()
+        )?;
+    }
+
+    if cmd.no_ignored {
+        return Ok(());
+    }
+
+    let ignored = list_ignored(git, source, excluded)?;
+    for entry in &ignored {
+        copy_path(&source.join(entry), &target.join(entry))?;
+    }
+    if !ignored.is_empty() {
+        writeln!(writer, "Copied {} ignored path(s):", ignored.len())?;
+        for entry in &ignored {
+            writeln!(writer, "  ! {}", entry)?;
+        }
+    }
+    Ok(())
+}
+
+/// ignore されたファイルを列挙する。ディレクトリはまとめて `dir/` として返す。
+/// 他の worktree を含むディレクトリは除外する。
+fn list_ignored(git: &GitRunner, source: &Path, excluded: &[PathBuf]) -> Result<Vec<String>> {
+    let output = common::run_git_in(
+        git,
+        source,
+        &[
+            "ls-files",
+            "--others",
+            "--ignored",
+            "--exclude-standard",
+            "--directory",
+            "-z",
+        ],
+    )?;
+
+    Ok(output
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .filter(|entry| {
+            let path = common::normalize_path(&source.join(entry.trim_end_matches('/')));
+            !excluded
+                .iter()
+                .any(|other| other.starts_with(&path) || path.starts_with(other))
+        })
+        .map(str::to_string)
+        .collect())
+}
+
+fn copy_path(src: &Path, dest: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(src)
+        .with_context(|| format!("failed to read {}", src.display()))?;
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+
+    if metadata.file_type().is_symlink() {
+        copy_symlink(src, dest)
+    } else if metadata.is_dir() {
+        fs::create_dir_all(dest)
+            .with_context(|| format!("failed to create {}", dest.display()))?;
+        for entry in fs::read_dir(src).with_context(|| format!("failed to read {}", src.display()))? {
+            let entry = entry?;
+            copy_path(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(src, dest)
+            .map(|_| ())
+            .with_context(|| format!("failed to copy file to {}", dest.display()))
+    }
+}
+
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
+    let link = fs::read_link(src).with_context(|| format!("failed to read {}", src.display()))?;
+    std::os::unix::fs::symlink(&link, dest)
+        .with_context(|| format!("failed to create symlink {}", dest.display()))
+}
+
+#[cfg(windows)]
+fn copy_symlink(src: &Path, dest: &Path) -> Result<()> {
+    let link = fs::read_link(src).with_context(|| format!("failed to read {}", src.display()))?;
+    let result = if src.is_dir() {
+        std::os::windows::fs::symlink_dir(&link, dest)
+    } else {
+        std::os::windows::fs::symlink_file(&link, dest)
+    };
+    result.with_context(|| format!("failed to create symlink {}", dest.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_path_preserves_directories_and_symlinks() {
+        let dir = TempDir::new().expect("temp dir");
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("pkg/bin")).expect("create src");
+        fs::write(src.join("pkg/index.js"), "module.exports = 1;").expect("write file");
+        std::os::unix::fs::symlink("../index.js", src.join("pkg/bin/run")).expect("symlink");
+
+        let dest = dir.path().join("dest");
+        copy_path(&src, &dest).expect("copy");
+
+        assert_eq!(
+            fs::read_to_string(dest.join("pkg/index.js")).unwrap(),
+            "module.exports = 1;"
+        );
+        let link = dest.join("pkg/bin/run");
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), PathBuf::from("../index.js"));
+    }
+}
@@ -0,0 +1,161 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::cli::ImportCommand;
+use crate::config::Config;
+use crate::config::types::CollisionPolicy;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::{common, create, metadata, pathmap};
+
+/// gwe の管理外にある worktree を `git worktree move` で `base_dir` の配置に移す
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &ImportCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
+    let current_path = common::normalize_path(repo.worktree_root());
+
+    let candidates: Vec<&WorktreeInfo> = match &cmd.path {
+        Some(path) => vec![find_worktree(&worktrees, &base_dir, path)?],
+        None => worktrees
+            .iter()
+            .filter(|info| {
+                !info.is_main
+                    && info.prunable.is_none()
+                    && !common::is_managed(info, &roots)
+                    && common::normalize_path(&info.path) != current_path
+            })
+            .collect(),
+    };
+
+    let mut stdout = io::stdout().lock();
+    if candidates.is_empty() {
+        writeln!(stdout, "No unmanaged worktrees to import")?;
+        return Ok(());
+    }
+
+    // 移動先は既存の worktree と、先に計画した移動先の両方と重ならないようにする
+    let mut taken: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
+    let mut plan = Vec::new();
+    for info in candidates {
+        let source = common::normalize_path(&info.path);
+        if source == current_path {
+            return Err(AppError::user(format!(
+                "cannot import the current worktree {}; run 'gwe import' from another worktree",
+                source.display()
+            ))
+            .into());
+        }
+        let identifier = match &info.branch {
+            Some(branch) => branch.clone(),
+            None => create::detached_identifier(git, &info.head)?,
+        };
+        let destination = destination_for(
+            &create::layout_path(repo, &base_dir, &identifier)?,
+            &taken,
+            config.path_collision,
+        )?;
+        taken.push(destination.clone());
+        plan.push((identifier, source, destination));
+    }
+
+    writeln!(stdout, "Importing {} worktree(s):", plan.len())?;
+    for (identifier, source, destination) in &plan {
+        writeln!(
+            stdout,
+            "  {}  {} -> {}",
+            identifier,
+            source.display(),
+            destination.display()
+        )?;
+    }
+    if cmd.dry_run {
+        return Ok(());
+    }
+
+    for (identifier, source, destination) in &plan {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        common::run_git_in(
+            git,
+            repo.main_root(),
+            &[
+                "worktree",
+                "move",
+                &source.to_string_lossy(),
+                &destination.to_string_lossy(),
+            ],
+        )?;
+
+        let mut meta = metadata::load(destination);
+        meta.imported_from = Some(source.clone());
+        metadata::save(destination, &meta)?;
+
+        writeln!(stdout, "Imported '{}' to {}", identifier, destination.display())?;
+    }
+    Ok(())
+}
+
+/// 指定パスの登録済み worktree。既に `base_dir` の配下にあるものは対象外
+fn find_worktree<'a>(
+    worktrees: &'a [WorktreeInfo],
+    base_dir: &Path,
+    path: &Path,
+) -> Result<&'a WorktreeInfo> {
+    let path = common::normalize_path(&std::env::current_dir()?.join(path));
+    let info = worktrees
+        .iter()
+        .find(|info| common::normalize_path(&info.path) == path)
+        .ok_or_else(|| {
+            AppError::user(format!(
+                "{} is not a registered worktree\nRun 'git worktree list' to see registered worktrees",
+                path.display()
+            ))
+        })?;
+    if info.is_main {
+        return Err(AppError::user("cannot import the main worktree").into());
+    }
+    if path.starts_with(common::normalize_path(base_dir)) {
+        return Err(AppError::user(format!(
+            "{} is already in gwe's layout",
+            path.display()
+        ))
+        .into());
+    }
+    Ok(info)
+}
+
+/// 移動先が既存のパスと重なる場合、`gwe.worktrees.onCollision` に従って連番を付けるかエラーにする
+pub fn destination_for(path: &Path, taken: &[PathBuf], policy: CollisionPolicy) -> Result<PathBuf> {
+    let is_taken = |candidate: &Path| {
+        let key = pathmap::collision_key(&common::normalize_path(candidate));
+        candidate.exists()
+            || taken
+                .iter()
+                .any(|other| pathmap::collision_key(other) == key)
+    };
+
+    let mut candidate = path.to_path_buf();
+    let mut attempt = 1;
+    while is_taken(&candidate) {
+        if policy == CollisionPolicy::Error {
+            return Err(AppError::user(format!(
+                "destination path already exists: {}\nMove it away or set gwe.worktrees.onCollision=suffix",
+                candidate.display()
+            ))
+            .into());
+        }
+        attempt += 1;
+        candidate = pathmap::with_suffix(path, attempt);
+    }
+    Ok(candidate)
+}
@@ -1,5 +1,5 @@
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use serde::Serialize;
@@ -9,11 +9,13 @@ use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
-use crate::worktree::common;
+use crate::worktree::process::ProcessTable;
+use crate::worktree::{common, metadata, safety};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct ListOptions {
     pub json: bool,
+    pub group: Option<String>,
 }
 
 pub fn run(
@@ -22,11 +24,22 @@ pub fn run(
     config: &Config,
     options: ListOptions,
 ) -> Result<()> {
-    let worktrees = list_worktrees(git)?;
+    let mut worktrees = list_worktrees(git)?;
+    // グループで絞り込む前に、メイン worktree からデフォルトブランチを決める
+    let base = safety::base_branch(config, &worktrees);
+    if let Some(group) = &options.group {
+        worktrees = metadata::group_members(&worktrees, group)
+            .into_iter()
+            .map(|(info, _)| info.clone())
+            .collect();
+        if worktrees.is_empty() {
+            return Err(AppError::user(format!("group '{}' not found", group)).into());
+        }
+    }
     let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
     let current_worktree = common::normalize_path(repo.worktree_root());
 
-    let rows = build_rows(&worktrees, git, &base_dir, &current_worktree)?;
+    let rows = build_rows(&worktrees, git, &base_dir, &current_worktree, base.as_deref())?;
 
     if options.json {
         output_json(&rows)?;
@@ -42,18 +55,51 @@ fn build_rows(
     git: &GitRunner,
     base_dir: &Path,
     current_worktree: &Path,
+    base: Option<&str>,
 ) -> Result<Vec<DisplayRow>> {
     let mut rows = Vec::with_capacity(worktrees.len());
+    let table = ProcessTable::scan();
+    let all_paths: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
 
     for info in worktrees {
         let abs_path = common::normalize_path(&info.path);
         let is_current = abs_path == current_worktree;
+        // メイン worktree の中にある worktree で動いているプロセスは、その worktree の分として数える
+        let nested: Vec<PathBuf> = all_paths
+            .iter()
+            .filter(|path| **path != abs_path && path.starts_with(&abs_path))
+            .cloned()
+            .collect();
+        let in_use = !table.inside(&abs_path, &nested).is_empty();
 
         let status = determine_status(git, &abs_path)?;
         let upstream = determine_upstream(git, &abs_path)?;
+        let upstream_gone = info
+            .branch
+            .as_deref()
+            .is_some_and(|branch| upstream_gone(git, branch));
+        let ahead_behind = match &upstream {
+            Some(upstream) => divergence(git, &abs_path, upstream)?,
+            None => None,
+        };
+        let base_divergence = match base {
+            Some(base) => divergence(git, &abs_path, base)?,
+            None => None,
+        };
+        let tag = if info.is_detached {
+            determine_tag(git, &abs_path)?
+        } else {
+            None
+        };
+
+        let sparse_patterns = determine_sparse_patterns(git, &abs_path)?;
+        let meta = metadata::load(&abs_path);
 
         let name = common::display_name(info, base_dir);
-        let branch_display = render_branch(info);
+        let branch_display = render_branch(info, tag.as_deref());
         let head_short = render_head(&info.head);
         let abs_path_display = abs_path.to_string_lossy().to_string();
 
@@ -64,9 +110,19 @@ fn build_rows(
             head: head_short,
             status,
             upstream,
+            upstream_gone,
+            ahead_behind,
+            base: base.filter(|_| base_divergence.is_some()).map(str::to_string),
+            base_divergence,
             abs_path: abs_path_display,
             is_main: info.is_main,
             is_current,
+            is_detached: info.is_detached,
+            in_use,
+            locked: info.locked.clone(),
+            sparse_patterns,
+            group: meta.group,
+            tool: meta.tool,
         });
     }
 
@@ -86,9 +142,95 @@ fn determine_status(git: &GitRunner, worktree_path: &Path) -> Result<String> {
 }
 
 fn determine_upstream(git: &GitRunner, worktree_path: &Path) -> Result<Option<String>> {
+    upstream_of(git, worktree_path, "HEAD")
+}
+
+/// `rev` の追跡先 (`origin/feature/x` など)。追跡先がなければ None
+pub fn upstream_of(git: &GitRunner, dir: &Path, rev: &str) -> Result<Option<String>> {
+    let upstream = format!("{}@{{u}}", rev);
+    match git.run_in(
+        dir,
+        ["rev-parse", "--abbrev-ref", "--symbolic-full-name", upstream.as_str()],
+    ) {
+        Ok(output) => {
+            let value = output.stdout().trim();
+            if value.is_empty() {
+                Ok(None)
+            } else {
+                Ok(Some(value.to_string()))
+            }
+        }
+        Err(GitError::CommandFailed { .. }) => Ok(None),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+/// 追跡していたリモートブランチが削除されているか
+pub fn upstream_gone(git: &GitRunner, branch: &str) -> bool {
+    let reference = format!("refs/heads/{}", branch);
+    git.run([
+        "for-each-ref",
+        "--format=%(upstream:track)",
+        reference.as_str(),
+    ])
+    .map(|output| output.stdout().trim() == "[gone]")
+    .unwrap_or(false)
+}
+
+/// HEAD が `other` より何コミット進んでいて (ahead)、何コミット遅れているか (behind)。
+/// `other` が解決できなければ None
+fn divergence(git: &GitRunner, worktree_path: &Path, other: &str) -> Result<Option<(usize, usize)>> {
+    let range = format!("HEAD...{}", other);
+    match git.run_in(
+        worktree_path,
+        ["rev-list", "--left-right", "--count", range.as_str(), "--"],
+    ) {
+        Ok(output) => {
+            let mut counts = output
+                .stdout()
+                .split_whitespace()
+                .map(|count| count.parse::<usize>().ok());
+            match (counts.next().flatten(), counts.next().flatten()) {
+                (Some(ahead), Some(behind)) => Ok(Some((ahead, behind))),
+                _ => Ok(None),
+            }
+        }
+        Err(GitError::CommandFailed { .. }) => Ok(None),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+/// sparse-checkout が有効な worktree ではそのパターン一覧を返す
+fn determine_sparse_patterns(git: &GitRunner, worktree_path: &Path) -> Result<Option<Vec<String>>> {
+    let enabled = match git.run_in(worktree_path, ["config", "--bool", "core.sparseCheckout"]) {
+        Ok(output) => output.stdout().trim() == "true",
+        Err(GitError::CommandFailed { .. }) => false,
+        Err(err) => return Err(AppError::git(err.to_string()).into()),
+    };
+    if !enabled {
+        return Ok(None);
+    }
+
+    match git.run_in(worktree_path, ["sparse-checkout", "list"]) {
+        Ok(output) => Ok(Some(
+            output
+                .stdout()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )),
+        Err(GitError::CommandFailed { .. }) => Ok(Some(Vec::new())),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+/// detached HEAD がタグを指している場合はそのタグ名を返す
+fn determine_tag(git: &GitRunner, worktree_path: &Path) -> Result<Option<String>> {
     match git.run_in(
         worktree_path,
-        ["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+        ["describe", "--tags", "--exact-match", "HEAD"],
     ) {
         Ok(output) => {
             let value = output.stdout().trim();
@@ -103,10 +245,11 @@ fn determine_upstream(git: &GitRunner, worktree_path: &Path) -> Result<Option<St
     }
 }
 
-fn render_branch(info: &WorktreeInfo) -> String {
-    match &info.branch {
-        Some(branch) => branch.clone(),
-        None => "detached".to_string(),
+fn render_branch(info: &WorktreeInfo, tag: Option<&str>) -> String {
+    match (&info.branch, tag) {
+        (Some(branch), _) => branch.clone(),
+        (None, Some(tag)) => format!("(detached {})", tag),
+        (None, None) => "(detached)".to_string(),
     }
 }
 
@@ -124,6 +267,8 @@ fn output_table(rows: &[DisplayRow]) -> Result<()> {
     let mut branch_width = "BRANCH".len();
     let mut status_width = "STATUS".len();
     let mut upstream_width = "UPSTREAM".len();
+    let mut ahead_behind_width = "AHEAD/BEHIND".len();
+    let mut base_width = "BASE".len();
     let mut abs_path_width = "ABS_PATH".len();
 
     let rendered_names: Vec<String> = rows
@@ -141,22 +286,24 @@ fn output_table(rows: &[DisplayRow]) -> Result<()> {
         let name_len = rendered_names[idx].len();
         path_width = path_width.max(name_len);
         branch_width = branch_width.max(row.branch_display.len());
-        status_width = status_width.max(row.status.len());
+        status_width = status_width.max(render_status(row).len());
         let upstream_len = row.upstream.as_deref().map_or(1, |s| s.len());
         upstream_width = upstream_width.max(upstream_len);
+        ahead_behind_width = ahead_behind_width.max(render_ahead_behind(row).len());
+        base_width = base_width.max(render_base(row).len());
         abs_path_width = abs_path_width.max(row.abs_path.len());
     }
 
     let mut stdout = io::stdout().lock();
     writeln!(
         stdout,
-        "{:<path_width$} {:<branch_width$} {:<8} {:<status_width$} {:<upstream_width$} {:<abs_path_width$}",
-        "PATH", "BRANCH", "HEAD", "STATUS", "UPSTREAM", "ABS_PATH"
+        "{:<path_width$} {:<branch_width$} {:<8} {:<status_width$} {:<upstream_width$} {:<ahead_behind_width$} {:<base_width$} {:<abs_path_width$}",
+        "PATH", "BRANCH", "HEAD", "STATUS", "UPSTREAM", "AHEAD/BEHIND", "BASE", "ABS_PATH"
     )?;
     writeln!(
         stdout,
-        "{:-<path_width$} {:-<branch_width$} {:-<8} {:-<status_width$} {:-<upstream_width$} {:-<abs_path_width$}",
-        "", "", "", "", "", ""
+        "{:-<path_width$} {:-<branch_width$} {:-<8} {:-<status_width$} {:-<upstream_width$} {:-<ahead_behind_width$} {:-<base_width$} {:-<abs_path_width$}",
+        "", "", "", "", "", "", "", ""
     )?;
 
     for (idx, row) in rows.iter().enumerate() {
@@ -164,14 +311,52 @@ fn output_table(rows: &[DisplayRow]) -> Result<()> {
         let upstream = row.upstream.as_deref().unwrap_or("-");
         writeln!(
             stdout,
-            "{:<path_width$} {:<branch_width$} {:<8} {:<status_width$} {:<upstream_width$} {:<abs_path_width$}",
-            name, row.branch_display, row.head, row.status, upstream, row.abs_path
+            "{:<path_width$} {:<branch_width$} {:<8} {:<status_width$} {:<upstream_width$} {:<ahead_behind_width$} {:<base_width$} {:<abs_path_width$}",
+            name,
+            row.branch_display,
+            row.head,
+            render_status(row),
+            upstream,
+            render_ahead_behind(row),
+            render_base(row),
+            row.abs_path
         )?;
     }
 
     Ok(())
 }
 
+/// 中でプロセスが動いている worktree には `in use`、ロックされた worktree には `locked` の印を付ける
+fn render_status(row: &DisplayRow) -> String {
+    let mut status = row.status.clone();
+    if row.in_use {
+        status.push_str(", in use");
+    }
+    match row.locked.as_deref() {
+        Some("") => status.push_str(", locked"),
+        Some(reason) => status.push_str(&format!(", locked: {}", reason)),
+        None => {}
+    }
+    status
+}
+
+/// 追跡先との差を `+2/-1` の形式で表示する。追跡先が削除されていれば `gone`
+fn render_ahead_behind(row: &DisplayRow) -> String {
+    if row.upstream_gone {
+        return "gone".to_string();
+    }
+    row.ahead_behind.map_or_else(|| "-".to_string(), render_counts)
+}
+
+/// デフォルトブランチとの差を `+2/-1` の形式で表示する
+fn render_base(row: &DisplayRow) -> String {
+    row.base_divergence.map_or_else(|| "-".to_string(), render_counts)
+}
+
+fn render_counts((ahead, behind): (usize, usize)) -> String {
+    format!("+{}/-{}", ahead, behind)
+}
+
 fn output_json(rows: &[DisplayRow]) -> Result<()> {
     let json_entries: Vec<JsonWorktree> = rows
         .iter()
@@ -181,10 +366,24 @@ fn output_json(rows: &[DisplayRow]) -> Result<()> {
             head: row.head.clone(),
             status: row.status.clone(),
             upstream: row.upstream.clone(),
+            upstream_gone: row.upstream_gone,
+            ahead: row.ahead_behind.map(|(ahead, _)| ahead),
+            behind: row.ahead_behind.map(|(_, behind)| behind),
+            base: row.base.clone(),
+            base_ahead: row.base_divergence.map(|(ahead, _)| ahead),
+            base_behind: row.base_divergence.map(|(_, behind)| behind),
             path: row.name.clone(),
             abs_path: row.abs_path.clone(),
             is_main: row.is_main,
             is_current: row.is_current,
+            is_detached: row.is_detached,
+            in_use: row.in_use,
+            locked: row.locked.is_some(),
+            lock_reason: row.locked.clone().filter(|reason| !reason.is_empty()),
+            sparse: row.sparse_patterns.is_some(),
+            sparse_patterns: row.sparse_patterns.clone().unwrap_or_default(),
+            group: row.group.clone(),
+            tool: row.tool.clone(),
         })
         .collect();
 
@@ -203,9 +402,23 @@ struct DisplayRow {
     head: String,
     status: String,
     upstream: Option<String>,
+    upstream_gone: bool,
+    /// 追跡先に対する (ahead, behind)
+    ahead_behind: Option<(usize, usize)>,
+    /// 比較したデフォルトブランチ
+    base: Option<String>,
+    /// デフォルトブランチに対する (ahead, behind)
+    base_divergence: Option<(usize, usize)>,
     abs_path: String,
     is_main: bool,
     is_current: bool,
+    is_detached: bool,
+    in_use: bool,
+    /// ロックの理由 (理由なしでロックされていれば空文字列)
+    locked: Option<String>,
+    sparse_patterns: Option<Vec<String>>,
+    group: Option<String>,
+    tool: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -215,8 +428,22 @@ struct JsonWorktree {
     head: String,
     status: String,
     upstream: Option<String>,
+    upstream_gone: bool,
+    ahead: Option<usize>,
+    behind: Option<usize>,
+    base: Option<String>,
+    base_ahead: Option<usize>,
+    base_behind: Option<usize>,
     path: String,
     abs_path: String,
     is_main: bool,
     is_current: bool,
+    is_detached: bool,
+    in_use: bool,
+    locked: bool,
+    lock_reason: Option<String>,
+    sparse: bool,
+    sparse_patterns: Vec<String>,
+    group: Option<String>,
+    tool: Option<String>,
 }
@@ -0,0 +1,93 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::cli::{LockCommand, UnlockCommand};
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::{common, rm};
+
+/// worktree をロックし、rm・prune・`git worktree prune` から守る
+pub fn lock(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &LockCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let info = find(repo, config, &worktrees, &cmd.worktree)?;
+    if let Some(reason) = &info.locked {
+        return Err(AppError::user(format!(
+            "'{}' is already locked{}",
+            cmd.worktree,
+            describe_reason(reason)
+        ))
+        .into());
+    }
+
+    lock_worktree(git, &info.path, cmd.reason.as_deref().unwrap_or_default())?;
+    writeln!(io::stdout().lock(), "Locked '{}'", cmd.worktree)?;
+    Ok(())
+}
+
+pub fn unlock(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &UnlockCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let info = find(repo, config, &worktrees, &cmd.worktree)?;
+    if info.locked.is_none() {
+        return Err(AppError::user(format!("'{}' is not locked", cmd.worktree)).into());
+    }
+
+    let path = info.path.to_string_lossy();
+    common::run_git_in(git, repo.main_root(), &["worktree", "unlock", path.as_ref()])?;
+    writeln!(io::stdout().lock(), "Unlocked '{}'", cmd.worktree)?;
+    Ok(())
+}
+
+/// `git worktree lock` を実行する。理由が空なら付けない
+pub fn lock_worktree(git: &GitRunner, path: &Path, reason: &str) -> Result<()> {
+    let path = path.to_string_lossy();
+    let mut args = vec!["worktree", "lock"];
+    let reason = reason.trim();
+    if !reason.is_empty() {
+        args.extend(["--reason", reason]);
+    }
+    args.push(path.as_ref());
+    common::run_git_in(git, git.repo().main_root(), &args)?;
+    Ok(())
+}
+
+/// ロックされていれば削除を拒否する。`-f` を 2 回指定した場合だけ削除を許す
+pub fn ensure_unlocked(info: &WorktreeInfo, label: &str, force: u8) -> Result<()> {
+    match &info.locked {
+        Some(reason) if force < 2 => Err(AppError::user(format!(
+            "refusing to remove {}: it is locked{}\nUnlock it with 'gwe unlock', or pass -ff to remove it anyway",
+            label,
+            describe_reason(reason)
+        ))
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// 複数削除の結果や prune の計画に表示するロックの理由
+pub fn locked_reason(reason: &str) -> String {
+    format!("locked{}", describe_reason(reason))
+}
+
+fn describe_reason(reason: &str) -> String {
+    if reason.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", reason)
+    }
+}
+
+fn find<'a>(
+    repo: &RepoContext,
+    config: &Config,
+    worktrees: &'a [WorktreeInfo],
+    target: &str,
+) -> Result<&'a WorktreeInfo> {
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
+    Ok(rm::find_target_worktree(worktrees, &base_dir, &roots, target)?)
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::git::worktree::WorktreeInfo;
+use crate::worktree::cleanup::CleanupContext;
+use crate::worktree::common;
+
+/// worktree ごとのメタデータを保存するファイル名 (worktree の管理ディレクトリ内)
+const METADATA_FILE: &str = "gwe.json";
+
+/// gwe が作成時に記録する worktree の付加情報。
+/// `<common-dir>/worktrees/<id>/gwe.json` に保存するため、worktree を削除すると一緒に消える。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorktreeMetadata {
+    /// 同時に作成された兄弟 worktree のグループ名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// グループ内での順番 (1 始まり)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// 作成時に起動したツール
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    /// `gwe import` で取り込む前のパス
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<PathBuf>,
+}
+
+/// リンクされた worktree の管理ディレクトリ (`.git` ファイルの gitdir) を返す
+pub fn admin_dir(worktree_path: &Path) -> Option<PathBuf> {
+    let dot_git = worktree_path.join(".git");
+    if !dot_git.is_file() {
+        return None;
+    }
+    let content = fs::read_to_string(&dot_git).ok()?;
+    let gitdir = content
+        .lines()
+        .find_map(|line| line.strip_prefix("gitdir:"))?
+        .trim();
+    let gitdir = PathBuf::from(gitdir);
+    Some(if gitdir.is_absolute() {
+        gitdir
+    } else {
+        worktree_path.join(gitdir)
+    })
+}
+
+/// メタデータを読み込む。記録がない worktree では既定値を返す
+pub fn load(worktree_path: &Path) -> WorktreeMetadata {
+    admin_dir(worktree_path)
+        .and_then(|dir| fs::read_to_string(dir.join(METADATA_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(worktree_path: &Path, metadata: &WorktreeMetadata) -> Result<()> {
+    let dir = admin_dir(worktree_path).with_context(|| {
+        format!("{} is not a linked worktree", worktree_path.display())
+    })?;
+    let file = dir.join(METADATA_FILE);
+    let json = serde_json::to_string_pretty(metadata).context("failed to serialize metadata")?;
+    fs::write(&file, json).with_context(|| format!("failed to write {}", file.display()))
+}
+
+/// worktree 削除時の後始末。通常は管理ディレクトリごと消えるが、
+/// prunable な worktree などで残った場合に備えて削除する
+pub fn cleanup(ctx: &CleanupContext) -> Result<()> {
+    let Some(dir) = &ctx.admin_dir else {
+        return Ok(());
+    };
+    let file = dir.join(METADATA_FILE);
+    if file.exists() {
+        fs::remove_file(&file).with_context(|| format!("failed to remove {}", file.display()))?;
+    }
+    Ok(())
+}
+
+/// グループに属する worktree をグループ内の順番で返す
+pub fn group_members<'a>(
+    worktrees: &'a [WorktreeInfo],
+    group: &str,
+) -> Vec<(&'a WorktreeInfo, WorktreeMetadata)> {
+    let mut members: Vec<_> = worktrees
+        .iter()
+        .filter(|info| !info.is_main)
+        .map(|info| (info, load(&common::normalize_path(&info.path))))
+        .filter(|(_, metadata)| metadata.group.as_deref() == Some(group))
+        .collect();
+    members.sort_by_key(|(_, metadata)| metadata.index.unwrap_or(usize::MAX));
+    members
+}
+
+/// ブランチ名の共通部分からグループ名を決める (`exp-1`, `exp-2` → `exp`)
+pub fn default_group_name(branches: &[String]) -> String {
+    let Some(first) = branches.first() else {
+        return String::new();
+    };
+    let mut prefix = first.as_str();
+    for branch in &branches[1..] {
+        let common_len = prefix
+            .char_indices()
+            .zip(branch.chars())
+            .take_while(|((_, a), b)| a == b)
+            .last()
+            .map(|((i, a), _)| i + a.len_utf8())
+            .unwrap_or(0);
+        prefix = &prefix[..common_len];
+    }
+
+    let trimmed = prefix.trim_end_matches(['-', '_', '/', '.']);
+    if trimmed.is_empty() {
+        first.clone()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn names(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn default_group_name_uses_common_prefix() {
+        assert_eq!(default_group_name(&names(&["exp-1", "exp-2", "exp-3"])), "exp");
+        assert_eq!(default_group_name(&names(&["try/a", "try/b"])), "try");
+        assert_eq!(default_group_name(&names(&["alpha", "beta"])), "alpha");
+        assert_eq!(default_group_name(&names(&["solo"])), "solo");
+    }
+
+    #[test]
+    fn save_and_load_round_trip_through_gitdir() {
+        let dir = TempDir::new().expect("temp dir");
+        let admin = dir.path().join("admin");
+        let worktree = dir.path().join("wt");
+        fs::create_dir_all(&admin).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", admin.display()),
+        )
+        .unwrap();
+
+        let metadata = WorktreeMetadata {
+            group: Some("exp".to_string()),
+            index: Some(2),
+            tool: Some("claude".to_string()),
+            imported_from: Some(PathBuf::from("/old/place")),
+        };
+        save(&worktree, &metadata).expect("save");
+
+        assert!(admin.join(METADATA_FILE).exists());
+        assert_eq!(load(&worktree), metadata);
+        assert_eq!(load(dir.path()), WorktreeMetadata::default());
+    }
+}
@@ -1,6 +1,24 @@
+pub mod cleanup;
 pub mod common;
 pub mod create;
+pub mod doctor;
+pub mod fork;
+pub mod import;
 pub mod list;
+pub mod lock;
+pub mod metadata;
+pub mod mv;
+pub mod naming;
+pub mod parallel;
+pub mod pathmap;
+pub mod process;
+pub mod prune;
+pub mod relocate;
 pub mod resolve;
 pub mod rm;
+pub mod safety;
+pub mod stash;
+pub mod submodule;
 pub mod tool;
+pub mod transfer;
+pub mod trash;
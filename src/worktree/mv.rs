@@ -0,0 +1,191 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use tracing::warn;
+
+use crate::cli::MvCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::list_worktrees;
+use crate::worktree::cleanup::{self, CleanupContext};
+use crate::worktree::rm::{self, RemoteBranch};
+use crate::worktree::{common, create, import, naming};
+
+/// worktree のブランチ名を変え、ディレクトリも新しいブランチ名の配置へ移動する。
+/// 途中で失敗した場合は、それまでの変更を元に戻す。
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &MvCommand) -> Result<()> {
+    let main_root = repo.main_root();
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(main_root);
+    let roots = config.managed_roots(main_root);
+    let info = rm::find_target_worktree(&worktrees, &base_dir, &roots, &cmd.worktree)?;
+
+    let old = info.branch.clone().ok_or_else(|| {
+        AppError::user(format!(
+            "'{}' has a detached HEAD; create a branch with 'git switch -c' first",
+            cmd.worktree
+        ))
+    })?;
+    let new = cmd.new_branch.as_str();
+    if info.locked.is_some() {
+        return Err(AppError::user(format!(
+            "'{}' is locked; unlock it before renaming",
+            old
+        ))
+        .into());
+    }
+    validate_new_branch(git, config, &old, new)?;
+
+    // gwe.worktrees.extraDirs にある worktree は、その場に置いたままブランチ名だけを変える
+    let source = common::normalize_path(&info.path);
+    let destination = if source.starts_with(common::normalize_path(&base_dir)) {
+        let others: Vec<PathBuf> = worktrees
+            .iter()
+            .map(|other| common::normalize_path(&other.path))
+            .filter(|path| *path != source)
+            .collect();
+        let path = import::destination_for(
+            &create::layout_path(repo, &base_dir, new)?,
+            &others,
+            config.path_collision,
+        )?;
+        Some(path).filter(|path| *path != source)
+    } else {
+        None
+    };
+
+    let remote = if cmd.remote {
+        Some(prepare_remote(git, &old)?)
+    } else {
+        None
+    };
+
+    common::run_git_in(git, main_root, &["branch", "-m", &old, new])?;
+
+    if let Some(destination) = &destination
+        && let Err(err) = move_worktree(git, &source, destination)
+    {
+        rollback(git, &old, new, None);
+        return Err(err.context("failed to move the worktree; the branch rename was rolled back"));
+    }
+
+    if let Some(remote) = &remote
+        && let Err(err) = rename_remote(git, remote, new)
+    {
+        rollback(git, &old, new, destination.as_deref().map(|dest| (dest, source.as_path())));
+        return Err(err.context("failed to rename the remote branch; the local rename was rolled back"));
+    }
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "Renamed branch '{}' to '{}'", old, new)?;
+    if let Some(remote) = &remote {
+        writeln!(
+            stdout,
+            "Renamed remote branch '{}' to '{}/{}'",
+            remote.upstream, remote.remote, new
+        )?;
+    }
+    if let Some(destination) = &destination {
+        let _ = cleanup::remove_empty_parents(&CleanupContext {
+            worktree_path: source.clone(),
+            admin_dir: None,
+            base_dir: common::normalize_path(&base_dir),
+        });
+        writeln!(stdout, "Moved worktree to {}", destination.display())?;
+        if source == common::normalize_path(repo.worktree_root()) {
+            writeln!(
+                io::stderr().lock(),
+                "Your shell is still in the old directory; run 'gwe cd {}' to follow it",
+                new
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_new_branch(git: &GitRunner, config: &Config, old: &str, new: &str) -> Result<()> {
+    if old == new {
+        return Err(AppError::user(format!("branch is already named '{}'", new)).into());
+    }
+    naming::validate_branch_name(git, config, new)?;
+    if common::branch_exists(git, new)? {
+        return Err(AppError::user(format!("branch '{}' already exists", new)).into());
+    }
+    Ok(())
+}
+
+/// 追跡先のリモートブランチを調べる。ローカルにないコミットがあれば名前を変えると失われるので拒否する
+fn prepare_remote(git: &GitRunner, branch: &str) -> Result<RemoteBranch> {
+    let remote = rm::tracked_remote(git, branch)?.ok_or_else(|| {
+        AppError::user(format!(
+            "'{}' does not track a branch on a remote; omit --remote",
+            branch
+        ))
+    })?;
+    let missing = rm::remote_only_commits(git, branch)?;
+    if missing > 0 {
+        return Err(AppError::user(format!(
+            "refusing to rename remote branch '{}': it has {} commit(s) not present locally\nPull them first",
+            remote.upstream, missing
+        ))
+        .into());
+    }
+    Ok(remote)
+}
+
+fn move_worktree(git: &GitRunner, source: &Path, destination: &Path) -> Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    common::run_git_in(
+        git,
+        git.repo().main_root(),
+        &[
+            "worktree",
+            "move",
+            &source.to_string_lossy(),
+            &destination.to_string_lossy(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// 新しい名前のブランチを push し、古いリモートブランチを削除する。
+/// `--atomic` でどちらか一方だけが反映されることを防ぐ。
+fn rename_remote(git: &GitRunner, remote: &RemoteBranch, new: &str) -> Result<()> {
+    let main_root = git.repo().main_root();
+    let push = format!("refs/heads/{}:refs/heads/{}", new, new);
+    let delete = format!(":{}", remote.reference);
+    common::run_git_in(
+        git,
+        main_root,
+        &["push", "--atomic", "--quiet", &remote.remote, &push, &delete],
+    )?;
+    let key = format!("branch.{}.merge", new);
+    let merge = format!("refs/heads/{}", new);
+    common::run_git_in(git, main_root, &["config", &key, &merge])?;
+    Ok(())
+}
+
+/// 失敗した時点までの変更を元に戻す。戻せなかった場合は手順を案内する
+fn rollback(git: &GitRunner, old: &str, new: &str, moved: Option<(&Path, &Path)>) {
+    if let Some((destination, source)) = moved
+        && move_worktree(git, destination, source).is_err()
+    {
+        warn!(
+            "could not move {} back to {}; run 'git worktree move' manually",
+            destination.display(),
+            source.display()
+        );
+    }
+    if common::run_git_in(git, git.repo().main_root(), &["branch", "-m", new, old]).is_err() {
+        warn!(
+            "could not rename branch '{}' back to '{}'; run 'git branch -m {} {}' manually",
+            new, old, new, old
+        );
+    }
+}
@@ -0,0 +1,268 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use regex::Regex;
+
+use crate::cli::ToolCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::runner::{GitError, GitRunner};
+use crate::worktree::common;
+
+/// `--name-from` で先頭の単語として認識するブランチ種別
+const BRANCH_KINDS: [&str; 11] = [
+    "feat", "feature", "fix", "bugfix", "hotfix", "chore", "docs", "refactor", "test", "perf",
+    "experiment",
+];
+
+/// `--name-from` で種別が分からないときに使うブランチ種別
+const DEFAULT_KIND: &str = "feature";
+
+/// スラッグの最大文字数 (種別部分を除く)
+const MAX_SLUG_CHARS: usize = 48;
+
+/// `-b` の値が省略された、または `--name-from` が指定された場合にブランチ名を決定する。
+/// どちらでもなければ `cmd` をそのまま返す。
+pub fn resolve_branch_name(
+    git: &GitRunner,
+    config: &Config,
+    cmd: &ToolCommand,
+    label: &str,
+) -> Result<ToolCommand> {
+    let mut resolved = cmd.clone();
+
+    if let Some(text) = &cmd.name_from {
+        let name = name_from_text(text)
+            .ok_or_else(|| AppError::user(format!("cannot derive a branch name from '{}'", text)))?;
+        resolved.branches = vec![apply_prefix(config, &name)];
+    } else {
+        for branch in resolved.branches.iter_mut() {
+            if branch.trim().is_empty() {
+                *branch = generate_unique_name(git, config, label)?;
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// 作成するブランチ名が `gwe.branch.prefix` / `gwe.branch.pattern` と
+/// `git check-ref-format` の規則を満たすか検査する
+pub fn validate_branch_name(git: &GitRunner, config: &Config, name: &str) -> Result<()> {
+    match git.run(["check-ref-format", "--branch", name]) {
+        Ok(_) => {}
+        Err(GitError::CommandFailed { .. }) => {
+            return Err(AppError::user(format!("'{}' is not a valid branch name", name)).into());
+        }
+        Err(err) => return Err(AppError::git(err.to_string()).into()),
+    }
+
+    if !config.branch_prefixes.is_empty()
+        && !config
+            .branch_prefixes
+            .iter()
+            .any(|prefix| name.starts_with(prefix.as_str()))
+    {
+        return Err(AppError::user(format!(
+            "branch '{}' must start with one of: {} (gwe.branch.prefix)",
+            name,
+            config.branch_prefixes.join(", ")
+        ))
+        .into());
+    }
+
+    if let Some(pattern) = &config.branch_pattern {
+        let regex = compile_pattern(pattern)?;
+        if !regex.is_match(name) {
+            return Err(AppError::user(format!(
+                "branch '{}' does not match gwe.branch.pattern '{}'",
+                name, pattern
+            ))
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// `gwe.branch.pattern` をブランチ名全体に一致する正規表現としてコンパイルする
+pub fn compile_pattern(pattern: &str) -> std::result::Result<Regex, AppError> {
+    Regex::new(&format!("^(?:{})$", pattern)).map_err(|err| {
+        AppError::config(format!("invalid gwe.branch.pattern '{}': {}", pattern, err))
+    })
+}
+
+/// "Fix login timeout on SSO" を "fix/login-timeout-on-sso" に変換する。
+/// 先頭の単語がブランチ種別でなければ "feature/" を付ける。
+pub fn name_from_text(text: &str) -> Option<String> {
+    let words: Vec<String> = text
+        .split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    let (kind, rest) = match words.split_first() {
+        Some((first, rest)) if BRANCH_KINDS.contains(&first.as_str()) && !rest.is_empty() => {
+            (first.as_str(), rest)
+        }
+        _ => (DEFAULT_KIND, words.as_slice()),
+    };
+
+    let mut slug = String::new();
+    for word in rest {
+        let extra = if slug.is_empty() { 0 } else { 1 };
+        if !slug.is_empty() && slug.chars().count() + extra + word.chars().count() > MAX_SLUG_CHARS {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.extend(word.chars().take(MAX_SLUG_CHARS));
+    }
+
+    if slug.is_empty() {
+        None
+    } else {
+        Some(format!("{}/{}", kind, slug))
+    }
+}
+
+/// `agent/claude-20261018-3f2a` のような既存ブランチと重ならない名前を生成する
+fn generate_unique_name(git: &GitRunner, config: &Config, label: &str) -> Result<String> {
+    let date = today();
+    for _ in 0..16 {
+        let name = apply_prefix(config, &generated_name(label, &date, random_hex()));
+        if !common::branch_exists(git, &name)? {
+            return Ok(name);
+        }
+    }
+    Err(AppError::internal("failed to generate a unique branch name").into())
+}
+
+fn generated_name(label: &str, date: &str, hex: u16) -> String {
+    if label.is_empty() {
+        format!("wip/{}-{:04x}", date, hex)
+    } else {
+        format!("agent/{}-{}-{:04x}", label, date, hex)
+    }
+}
+
+/// 生成した名前が `gwe.branch.prefix` のどれにも当てはまらなければ先頭の prefix を付ける
+fn apply_prefix(config: &Config, name: &str) -> String {
+    match config.branch_prefixes.first() {
+        Some(prefix)
+            if !config
+                .branch_prefixes
+                .iter()
+                .any(|p| name.starts_with(p.as_str())) =>
+        {
+            format!("{}{}", prefix, name)
+        }
+        _ => name.to_string(),
+    }
+}
+
+pub fn random_hex() -> u16 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.write_u32(std::process::id());
+    hasher.finish() as u16
+}
+
+/// UTC の日付を YYYYMMDD 形式で返す
+pub fn today() -> String {
+    let days = common::unix_now() / 86_400;
+    let (year, month, day) = civil_from_days(days as i64);
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// 1970-01-01 からの日数をグレゴリオ暦の (年, 月, 日) に変換する
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_from_text_uses_leading_kind() {
+        assert_eq!(
+            name_from_text("Fix login timeout on SSO").as_deref(),
+            Some("fix/login-timeout-on-sso")
+        );
+        assert_eq!(
+            name_from_text("docs: update README (v2)").as_deref(),
+            Some("docs/update-readme-v2")
+        );
+    }
+
+    #[test]
+    fn name_from_text_defaults_to_feature() {
+        assert_eq!(
+            name_from_text("Support dark mode!").as_deref(),
+            Some("feature/support-dark-mode")
+        );
+        assert_eq!(name_from_text("fix").as_deref(), Some("feature/fix"));
+        assert_eq!(name_from_text("  ?!  "), None);
+    }
+
+    #[test]
+    fn name_from_text_limits_length_on_word_boundary() {
+        let name = name_from_text(&"word ".repeat(30)).unwrap();
+        let slug = name.strip_prefix("feature/").unwrap();
+        assert!(slug.chars().count() <= MAX_SLUG_CHARS);
+        assert!(!slug.ends_with('-'));
+    }
+
+    #[test]
+    fn apply_prefix_keeps_matching_names() {
+        let config = Config {
+            branch_prefixes: vec!["fix/".to_string(), "feature/".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(apply_prefix(&config, "feature/x"), "feature/x");
+        assert_eq!(apply_prefix(&config, "agent/x"), "fix/agent/x");
+        assert_eq!(apply_prefix(&Config::default(), "agent/x"), "agent/x");
+    }
+
+    #[test]
+    fn generated_name_has_label_date_and_hex() {
+        assert_eq!(
+            generated_name("claude", "20261018", 0x3f2a),
+            "agent/claude-20261018-3f2a"
+        );
+        assert_eq!(generated_name("", "20261018", 0x00ff), "wip/20261018-00ff");
+    }
+
+    #[test]
+    fn civil_from_days_converts_epoch_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(20_744), (2026, 10, 18));
+    }
+
+    #[test]
+    fn compile_pattern_anchors_whole_name() {
+        let regex = compile_pattern("(feature|fix)/[a-z0-9-]+").unwrap();
+        assert!(regex.is_match("fix/login"));
+        assert!(!regex.is_match("user/fix/login"));
+        assert!(compile_pattern("(").is_err());
+    }
+}
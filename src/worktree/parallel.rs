@@ -0,0 +1,143 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+use std::thread;
+
+/// `items` を最大 `jobs` 個のスレッドで処理し、入力と同じ順序で結果を返す
+pub fn run_limited<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new(items.iter().map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    let result = f(index, item);
+                    results.lock().unwrap_or_else(PoisonError::into_inner)[index] = Some(result);
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner)
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+/// 行ごとに `[2/5] ` のような接頭辞を付けて共有の出力先へ書き込む。
+/// 1 行単位で書き込むため、並列に動く兄弟の出力が行の途中で混ざらない。
+pub struct PrefixedWriter<'a, W: Write> {
+    prefix: String,
+    sink: &'a Mutex<W>,
+    pending: Vec<u8>,
+}
+
+impl<'a, W: Write> PrefixedWriter<'a, W> {
+    pub fn new(prefix: impl Into<String>, sink: &'a Mutex<W>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            sink,
+            pending: Vec::new(),
+        }
+    }
+
+    fn write_line(&self, line: &[u8]) -> io::Result<()> {
+        let mut sink = self.sink.lock().unwrap_or_else(PoisonError::into_inner);
+        if line.is_empty() {
+            writeln!(sink, "{}", self.prefix.trim_end())
+        } else {
+            sink.write_all(self.prefix.as_bytes())?;
+            sink.write_all(line)?;
+            sink.write_all(b"\n")
+        }
+    }
+}
+
+impl<W: Write> Write for PrefixedWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        while let Some(pos) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=pos).collect();
+            let line = line.strip_suffix(b"\n").unwrap_or(&line);
+            self.write_line(line.strip_suffix(b"\r").unwrap_or(line))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.write_line(&line)?;
+        }
+        self.sink
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .flush()
+    }
+}
+
+impl<W: Write> Drop for PrefixedWriter<'_, W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn run_limited_keeps_input_order() {
+        let items: Vec<u64> = (0..8).collect();
+        let results = run_limited(&items, 3, |index, item| {
+            thread::sleep(Duration::from_millis(8 - item));
+            (index, item * 10)
+        });
+        let expected: Vec<(usize, u64)> = (0..8).map(|i| (i as usize, i * 10)).collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn run_limited_never_exceeds_job_limit() {
+        let running = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let items = vec![(); 10];
+        run_limited(&items, 2, |_, _| {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(5));
+            running.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+
+    #[test]
+    fn prefixed_writer_prefixes_each_line() {
+        let sink = Mutex::new(Vec::new());
+        {
+            let mut writer = PrefixedWriter::new("[2/5] ", &sink);
+            write!(writer, "Created ").unwrap();
+            writeln!(writer, "worktree\n\nnext").unwrap();
+            write!(writer, "partial").unwrap();
+        }
+        let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            output,
+            "[2/5] Created worktree\n[2/5]\n[2/5] next\n[2/5] partial\n"
+        );
+    }
+}
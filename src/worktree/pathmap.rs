@@ -0,0 +1,193 @@
+// ブランチ名から worktree のディレクトリパスへの変換。
+// Windows / macOS / Linux のどのファイルシステムでも作成できるパスを作る。
+
+use std::path::{Path, PathBuf};
+
+use unicode_normalization::UnicodeNormalization;
+
+/// 1 セグメントあたりの最大文字数。超えた分は切り詰めてハッシュを付与する
+const MAX_SEGMENT_CHARS: usize = 64;
+
+const INVALID_CHARS: [char; 8] = ['<', '>', ':', '"', '|', '?', '*', '\\'];
+
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// ブランチ名 (`feature/auth` など) を base_dir からの相対パスに変換する
+pub fn branch_to_relative_path(name: &str) -> PathBuf {
+    let mut result = PathBuf::new();
+    for segment in name.split(['/', '\\']) {
+        if segment.is_empty() {
+            continue;
+        }
+        result.push(sanitize_segment(segment));
+    }
+    if result.as_os_str().is_empty() {
+        result.push("_");
+    }
+    result
+}
+
+/// パスの 1 セグメントを移植性のある名前に変換する
+pub fn sanitize_segment(segment: &str) -> String {
+    let normalized: String = segment.nfc().collect();
+    if normalized.is_empty() || normalized == "." || normalized == ".." {
+        return "_".to_string();
+    }
+
+    let mut sanitized: String = normalized
+        .chars()
+        .map(|ch| {
+            if INVALID_CHARS.contains(&ch) || ch.is_control() {
+                '_'
+            } else {
+                ch
+            }
+        })
+        .collect();
+
+    // Windows は末尾のドットと空白を黙って取り除くため、別の文字に置き換える
+    let trimmed_len = sanitized.trim_end_matches(['.', ' ']).len();
+    if trimmed_len < sanitized.len() {
+        let removed = sanitized.len() - trimmed_len;
+        sanitized.truncate(trimmed_len);
+        sanitized.push_str(&"_".repeat(removed));
+    }
+
+    if is_reserved_name(&sanitized) {
+        sanitized.insert(0, '_');
+    }
+
+    if sanitized.chars().count() > MAX_SEGMENT_CHARS {
+        let hash = format!("{:08x}", fnv1a(normalized.as_bytes()));
+        let keep = MAX_SEGMENT_CHARS - hash.len() - 1;
+        let mut shortened: String = sanitized.chars().take(keep).collect();
+        shortened.push('-');
+        shortened.push_str(&hash);
+        sanitized = shortened;
+    }
+
+    sanitized
+}
+
+/// 大文字小文字を区別しない・Unicode 正規化を行うファイルシステムでの同一性を判定するキー
+pub fn collision_key(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .nfc()
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// 末尾のディレクトリ名に `-<n>` を付けたパスを返す
+pub fn with_suffix(path: &Path, n: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{}-{}", name, n))
+}
+
+/// `CON` や `nul.txt` のような Windows の予約デバイス名かどうか
+fn is_reserved_name(segment: &str) -> bool {
+    let stem = segment.split('.').next().unwrap_or(segment).trim_end();
+    RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in bytes {
+        hash ^= u32::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_branch_into_segments() {
+        assert_eq!(
+            branch_to_relative_path("feature/auth"),
+            PathBuf::from("feature").join("auth")
+        );
+        assert_eq!(
+            branch_to_relative_path("feature//a\\b"),
+            PathBuf::from("feature").join("a").join("b")
+        );
+        assert_eq!(branch_to_relative_path("/"), PathBuf::from("_"));
+    }
+
+    #[test]
+    fn replaces_invalid_characters_and_dot_segments() {
+        assert_eq!(sanitize_segment("a<b>c:d|e?f*g\"h"), "a_b_c_d_e_f_g_h");
+        assert_eq!(sanitize_segment("tab\there"), "tab_here");
+        assert_eq!(sanitize_segment("."), "_");
+        assert_eq!(sanitize_segment(".."), "_");
+        assert_eq!(sanitize_segment(".hidden"), ".hidden");
+    }
+
+    #[test]
+    fn escapes_windows_reserved_names() {
+        assert_eq!(sanitize_segment("CON"), "_CON");
+        assert_eq!(sanitize_segment("nul"), "_nul");
+        assert_eq!(sanitize_segment("com1.txt"), "_com1.txt");
+        assert_eq!(sanitize_segment("console"), "console");
+        assert_eq!(sanitize_segment("COM10"), "COM10");
+    }
+
+    #[test]
+    fn replaces_trailing_dots_and_spaces() {
+        assert_eq!(sanitize_segment("release."), "release_");
+        assert_eq!(sanitize_segment("wip. "), "wip__");
+        assert_eq!(sanitize_segment("v1.2"), "v1.2");
+    }
+
+    #[test]
+    fn shortens_overlong_segments_with_stable_hash() {
+        let long = "x".repeat(200);
+        let first = sanitize_segment(&long);
+        assert_eq!(first.chars().count(), MAX_SEGMENT_CHARS);
+        assert_eq!(first, sanitize_segment(&long));
+
+        let other = sanitize_segment(&format!("{}y", "x".repeat(199)));
+        assert_ne!(first, other, "different names keep different hashes");
+    }
+
+    #[test]
+    fn normalizes_unicode_to_nfc() {
+        let composed = "caf\u{e9}";
+        let decomposed = "cafe\u{301}";
+        assert_eq!(sanitize_segment(decomposed), composed);
+        assert_eq!(
+            collision_key(Path::new(decomposed)),
+            collision_key(Path::new(composed))
+        );
+    }
+
+    #[test]
+    fn collision_key_ignores_case() {
+        assert_eq!(
+            collision_key(&branch_to_relative_path("Feature/X")),
+            collision_key(&branch_to_relative_path("feature/x"))
+        );
+        assert_ne!(
+            collision_key(&branch_to_relative_path("feature/x")),
+            collision_key(&branch_to_relative_path("feature/y"))
+        );
+    }
+
+    #[test]
+    fn with_suffix_changes_only_last_segment() {
+        assert_eq!(
+            with_suffix(Path::new("repo/feature/x"), 2),
+            PathBuf::from("repo/feature/x-2")
+        );
+    }
+}
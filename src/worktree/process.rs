@@ -0,0 +1,249 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+
+use crate::error::AppError;
+
+/// SIGTERM の後、SIGKILL を送るまで待つ時間
+const TERM_GRACE: Duration = Duration::from_secs(5);
+/// SIGKILL の後に終了を待つ時間
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// worktree 内で動いているプロセス
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// コマンドライン (取得できなければプロセス名)
+    pub command: String,
+}
+
+impl ProcessInfo {
+    /// `12345 (npm run dev)` の形式で表示する
+    pub fn describe(&self) -> String {
+        format!("{} ({})", self.pid, self.command)
+    }
+}
+
+/// 実行中のプロセスと、その作業ディレクトリ・開いているファイルのスナップショット。
+/// 複数の worktree を調べるときに /proc を一度だけ走査するために使う。
+#[derive(Debug, Default)]
+pub struct ProcessTable {
+    entries: Vec<(ProcessInfo, Vec<PathBuf>)>,
+}
+
+impl ProcessTable {
+    /// gwe 自身と、gwe を起動したシェル (親プロセス) を除いて走査する
+    pub fn scan() -> Self {
+        let own = std::process::id();
+        let parent = parent_pid(own);
+        let entries = scan()
+            .into_iter()
+            .filter(|(process, _)| process.pid != own && Some(process.pid) != parent)
+            .collect();
+        Self { entries }
+    }
+
+    /// 作業ディレクトリか開いているファイルが `root` の配下にあるプロセス。
+    /// `exclude` の配下 (入れ子になった別の worktree など) だけを使っているものは除く。
+    pub fn inside(&self, root: &Path, exclude: &[PathBuf]) -> Vec<ProcessInfo> {
+        let root = canonical(root);
+        let exclude: Vec<PathBuf> = exclude.iter().map(|path| canonical(path)).collect();
+        self.entries
+            .iter()
+            .filter(|(_, paths)| {
+                paths.iter().any(|path| {
+                    path.starts_with(&root) && !exclude.iter().any(|other| path.starts_with(other))
+                })
+            })
+            .map(|(process, _)| process.clone())
+            .collect()
+    }
+}
+
+/// プロセスに SIGTERM を送り、終了しなければ SIGKILL を送る
+pub fn terminate(processes: &[ProcessInfo]) -> Result<()> {
+    let pids: Vec<u32> = processes.iter().map(|process| process.pid).collect();
+    signal("TERM", &pids);
+    let remaining = wait_for_exit(&pids, TERM_GRACE);
+    if remaining.is_empty() {
+        return Ok(());
+    }
+
+    signal("KILL", &remaining);
+    let remaining = wait_for_exit(&remaining, KILL_GRACE);
+    if remaining.is_empty() {
+        return Ok(());
+    }
+
+    let pids: Vec<String> = remaining.iter().map(u32::to_string).collect();
+    Err(AppError::user(format!("failed to stop process(es): {}", pids.join(", "))).into())
+}
+
+/// 削除を拒否するときのエラーメッセージ
+pub fn busy_message(target: &str, processes: &[ProcessInfo]) -> String {
+    let mut message = format!("refusing to remove {}: processes are running inside it:", target);
+    for process in processes {
+        message.push_str("\n  - ");
+        message.push_str(&process.describe());
+    }
+    message.push_str("\nStop them first, or pass --kill to terminate them");
+    message
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn signal(name: &str, pids: &[u32]) {
+    if pids.is_empty() {
+        return;
+    }
+    // 既に終了したプロセスがあると失敗するが、終了の確認は呼び出し側で行う
+    let _ = std::process::Command::new("kill")
+        .arg(format!("-{}", name))
+        .args(pids.iter().map(u32::to_string))
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status();
+}
+
+/// `timeout` まで待ち、まだ動いているプロセスを返す
+fn wait_for_exit(pids: &[u32], timeout: Duration) -> Vec<u32> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let alive: Vec<u32> = pids.iter().copied().filter(|pid| is_alive(*pid)).collect();
+        if alive.is_empty() || Instant::now() >= deadline {
+            return alive;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn scan() -> Vec<(ProcessInfo, Vec<PathBuf>)> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut processes: Vec<(ProcessInfo, Vec<PathBuf>)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let dir = entry.path();
+            let mut paths: Vec<PathBuf> = std::fs::read_link(dir.join("cwd")).into_iter().collect();
+            // 他ユーザーのプロセスは読めないので、読めるものだけを調べる
+            if let Ok(fds) = std::fs::read_dir(dir.join("fd")) {
+                paths.extend(
+                    fds.flatten()
+                        .filter_map(|fd| std::fs::read_link(fd.path()).ok())
+                        .filter(|target| target.is_absolute()),
+                );
+            }
+            if paths.is_empty() {
+                return None;
+            }
+            Some((ProcessInfo { pid, command: command_of(&dir) }, paths))
+        })
+        .collect();
+    processes.sort_by_key(|(process, _)| process.pid);
+    processes
+}
+
+/// /proc がない環境では検出しない
+#[cfg(not(target_os = "linux"))]
+fn scan() -> Vec<(ProcessInfo, Vec<PathBuf>)> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn command_of(dir: &Path) -> String {
+    let cmdline = std::fs::read(dir.join("cmdline")).unwrap_or_default();
+    let args: Vec<String> = cmdline
+        .split(|byte| *byte == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    if !args.is_empty() {
+        return args.join(" ");
+    }
+    std::fs::read_to_string(dir.join("comm"))
+        .map(|name| name.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// `pid (comm) state ppid ...` の comm には空白や括弧が含まれうるので最後の ')' から読む
+#[cfg(target_os = "linux")]
+fn stat_fields(pid: u32) -> Option<Vec<String>> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    let rest = &stat[stat.rfind(')')? + 1..];
+    Some(rest.split_whitespace().map(str::to_string).collect())
+}
+
+#[cfg(target_os = "linux")]
+fn parent_pid(pid: u32) -> Option<u32> {
+    stat_fields(pid)?.get(1)?.parse().ok()
+}
+
+/// 終了して回収待ちのゾンビは動いていないものとみなす
+#[cfg(target_os = "linux")]
+fn is_alive(pid: u32) -> bool {
+    stat_fields(pid).is_some_and(|fields| fields.first().is_some_and(|state| state != "Z"))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn parent_pid(_pid: u32) -> Option<u32> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_alive(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_message_lists_processes_and_suggests_kill() {
+        let processes = vec![ProcessInfo {
+            pid: 42,
+            command: "npm run dev".to_string(),
+        }];
+        let message = busy_message("'feature/x'", &processes);
+        assert!(message.starts_with("refusing to remove 'feature/x'"));
+        assert!(message.contains("  - 42 (npm run dev)"));
+        assert!(message.ends_with("pass --kill to terminate them"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn finds_and_terminates_processes_running_inside_directory() {
+        use std::process::Command;
+        use tempfile::TempDir;
+
+        let dir = TempDir::new().expect("temp dir");
+        let nested = dir.path().join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .current_dir(&nested)
+            .spawn()
+            .expect("spawn sleep");
+
+        let table = ProcessTable::scan();
+        let found = table.inside(dir.path(), &[]);
+        assert!(found.iter().any(|process| process.pid == child.id()));
+        assert!(found.iter().all(|process| process.pid != std::process::id()));
+        assert!(
+            table
+                .inside(dir.path(), std::slice::from_ref(&nested))
+                .iter()
+                .all(|process| process.pid != child.id()),
+            "processes inside excluded directories are ignored"
+        );
+
+        terminate(&found).expect("terminate");
+        assert!(child.try_wait().expect("wait").is_some());
+    }
+}
@@ -0,0 +1,249 @@
+use std::fs;
+use std::io::{self, Write};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+
+use crate::cli::PruneCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::process::{ProcessInfo, ProcessTable};
+use crate::worktree::{common, list, lock, metadata, rm, safety};
+
+/// 削除計画の 1 行
+struct PlanItem<'a> {
+    info: &'a WorktreeInfo,
+    name: String,
+    reasons: Vec<String>,
+    /// 削除しない理由 (None なら削除する)
+    skip: Option<String>,
+    /// 失われる作業があり、ゴミ箱へ退避してから削除する
+    backup: bool,
+    /// `--kill` で削除前に終了させるプロセス
+    processes: Vec<ProcessInfo>,
+}
+
+/// 条件に合う管理下の worktree をまとめて削除する
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &PruneCommand) -> Result<()> {
+    // 条件の指定がなければ、作業が終わったことが確実なものだけを対象にする
+    let all = !cmd.merged && !cmd.gone && !cmd.prunable && cmd.stale.is_none();
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
+    let current_path = common::normalize_path(repo.worktree_root());
+    let worktrees = list_worktrees(git)?;
+    let base = safety::base_branch(config, &worktrees);
+    let table = ProcessTable::scan();
+
+    let mut plan = Vec::new();
+    for info in &worktrees {
+        if info.is_main
+            || !common::is_managed(info, &roots)
+            || common::normalize_path(&info.path) == current_path
+        {
+            continue;
+        }
+
+        let mut reasons = Vec::new();
+        if (all || cmd.prunable) && info.prunable.is_some() {
+            reasons.push("prunable".to_string());
+        }
+        if let Some(branch) = info.branch.as_deref() {
+            if (all || cmd.merged)
+                && let Some(base) = base.as_deref().filter(|base| *base != branch)
+                && is_merged(git, branch, base)
+                // 作ったばかりのブランチも base に含まれるので、既定では自分のコミットがあるものに限る
+                && (cmd.merged || has_own_commits(git, branch))
+            {
+                reasons.push(format!("merged into {}", base));
+            }
+            if (all || cmd.gone) && list::upstream_gone(git, branch) {
+                reasons.push("upstream gone".to_string());
+            }
+        }
+        if let Some(days) = cmd.stale
+            && let Some(idle) = idle_days(git, info)
+            && idle >= days
+        {
+            reasons.push(format!("idle {} days", idle));
+        }
+        if reasons.is_empty() {
+            continue;
+        }
+
+        let mut item = PlanItem {
+            info,
+            name: common::display_name(info, &base_dir),
+            reasons,
+            skip: None,
+            backup: false,
+            processes: table.inside(&info.path, &[]),
+        };
+        if let Some(reason) = info.locked.as_deref().filter(|_| cmd.force < 2) {
+            item.skip = Some(lock::locked_reason(reason));
+        } else if !item.processes.is_empty() && !cmd.kill {
+            item.skip = Some(rm::in_use_reason(&item.processes));
+        } else {
+            let work = safety::unsaved_work(git, config, &worktrees, info, cmd.with_branch)?;
+            if !work.is_empty() {
+                if cmd.force > 0 {
+                    item.backup = true;
+                } else {
+                    item.skip = Some(format!("would lose {}", work.describe().join(", ")));
+                }
+            }
+        }
+        plan.push(item);
+    }
+
+    let mut stdout = io::stdout().lock();
+    if plan.is_empty() {
+        writeln!(stdout, "Nothing to prune")?;
+        return Ok(());
+    }
+    write_plan(&mut stdout, &plan)?;
+
+    let targets: Vec<&PlanItem> = plan.iter().filter(|item| item.skip.is_none()).collect();
+    if targets.is_empty() || cmd.dry_run {
+        return Ok(());
+    }
+
+    if !cmd.yes {
+        let prompt = format!("Remove {} worktree(s)?", targets.len());
+        if !safety::confirm(&prompt)? {
+            return Err(AppError::user(
+                "prune cancelled; pass --yes to prune without confirmation",
+            )
+            .into());
+        }
+    }
+
+    for item in &targets {
+        rm::stop_processes(&mut stdout, &format!("'{}'", item.name), &item.processes)?;
+        if item.backup {
+            rm::move_to_trash(&mut stdout, repo, git, config, item.info)?;
+        }
+        rm::remove_one(
+            &mut stdout,
+            git,
+            &base_dir,
+            item.info,
+            &item.name,
+            cmd.with_branch,
+        )?;
+    }
+    writeln!(stdout, "Pruned {} worktree(s)", targets.len())?;
+    Ok(())
+}
+
+fn write_plan<W: Write>(writer: &mut W, plan: &[PlanItem]) -> Result<()> {
+    let rows: Vec<[String; 4]> = plan
+        .iter()
+        .map(|item| {
+            [
+                item.name.clone(),
+                item.info.branch.clone().unwrap_or_else(|| "-".to_string()),
+                item.reasons.join(", "),
+                match &item.skip {
+                    Some(reason) => format!("skip ({})", reason),
+                    None if !item.processes.is_empty() => {
+                        format!("remove (stop {} process(es))", item.processes.len())
+                    }
+                    None if item.backup => "remove (to trash)".to_string(),
+                    None => "remove".to_string(),
+                },
+            ]
+        })
+        .collect();
+
+    let headers = ["WORKTREE", "BRANCH", "REASON", "ACTION"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    writeln!(
+        writer,
+        "{:<w0$} {:<w1$} {:<w2$} {}",
+        headers[0],
+        headers[1],
+        headers[2],
+        headers[3],
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2]
+    )?;
+    writeln!(
+        writer,
+        "{:-<w0$} {:-<w1$} {:-<w2$} {:-<w3$}",
+        "",
+        "",
+        "",
+        "",
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2],
+        w3 = widths[3]
+    )?;
+    for row in &rows {
+        writeln!(
+            writer,
+            "{:<w0$} {:<w1$} {:<w2$} {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )?;
+    }
+    Ok(())
+}
+
+fn is_merged(git: &GitRunner, branch: &str, base: &str) -> bool {
+    let branch_ref = format!("refs/heads/{}", branch);
+    let base_ref = format!("refs/heads/{}", base);
+    git.run([
+        "merge-base",
+        "--is-ancestor",
+        branch_ref.as_str(),
+        base_ref.as_str(),
+    ])
+    .is_ok()
+}
+
+/// ブランチを作成してからコミットが積まれたか。reflog の最も古い記録を作成時点とみなし、
+/// reflog がなければ判断できないので false を返す
+fn has_own_commits(git: &GitRunner, branch: &str) -> bool {
+    let branch_ref = format!("refs/heads/{}", branch);
+    let Ok(output) = git.run(["reflog", "show", "--format=%H", branch_ref.as_str()]) else {
+        return false;
+    };
+    let Some(created) = output.stdout().lines().map(str::trim).rfind(|line| !line.is_empty()) else {
+        return false;
+    };
+    let range = format!("{}..{}", created, branch_ref);
+    git.run(["rev-list", "--count", range.as_str()])
+        .is_ok_and(|output| output.stdout().trim().parse::<u64>().is_ok_and(|count| count > 0))
+}
+
+/// 最後の活動 (HEAD のコミット日時か index の更新日時の新しい方) からの経過日数
+fn idle_days(git: &GitRunner, info: &WorktreeInfo) -> Option<u64> {
+    let committed = git
+        .run(["log", "-1", "--format=%ct", info.head.as_str()])
+        .ok()
+        .and_then(|output| output.stdout().trim().parse::<u64>().ok());
+    let touched = metadata::admin_dir(&common::normalize_path(&info.path))
+        .and_then(|dir| fs::metadata(dir.join("index")).ok())
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs());
+    let last = committed.into_iter().chain(touched).max()?;
+
+    Some(common::unix_now().saturating_sub(last) / 86_400)
+}
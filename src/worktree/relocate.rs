@@ -0,0 +1,308 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::cli::RelocateCommand;
+use crate::config::Config;
+use crate::config::types::resolve_dir;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::{WorktreeInfo, list_worktrees};
+use crate::worktree::cleanup::{self, CleanupContext};
+use crate::worktree::process::ProcessTable;
+use crate::worktree::{common, import, lock, pathmap, rm, safety, trash};
+
+/// `worktree.useRelativePaths` と `git worktree repair --relative-paths` が使える git のバージョン
+const RELATIVE_PATHS_SINCE: (u32, u32) = (2, 48);
+
+struct PlanItem {
+    name: String,
+    source: PathBuf,
+    destination: PathBuf,
+    /// 移動元の `<旧 base_dir>/<リポジトリ名>`
+    old_root: PathBuf,
+    skip: Option<String>,
+}
+
+/// `gwe.worktrees.dir` や配置規則の変更後に、管理下の worktree を新しい配置へ移動する
+pub fn run(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &RelocateCommand) -> Result<()> {
+    // 移動を始めてから失敗しないよう、git のバージョンは先に確認する
+    if cmd.relative_paths {
+        ensure_relative_paths_supported(git)?;
+    }
+
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let layout_root = common::normalize_path(&base_dir.join(repo.repo_name()));
+    let old_root = cmd
+        .from
+        .as_ref()
+        .map(|dir| common::normalize_path(&resolve_dir(dir, repo.main_root()).join(repo.repo_name())));
+    // gwe.worktrees.extraDirs の worktree はその場で管理するので動かさない
+    let extra_roots: Vec<PathBuf> = config
+        .managed_roots(repo.main_root())
+        .iter()
+        .skip(1)
+        .map(|root| common::normalize_path(root))
+        .collect();
+    let current_path = common::normalize_path(repo.worktree_root());
+    let worktrees = list_worktrees(git)?;
+    let table = ProcessTable::scan();
+
+    let mut taken: Vec<PathBuf> = worktrees
+        .iter()
+        .map(|info| common::normalize_path(&info.path))
+        .collect();
+    let mut plan = Vec::new();
+    for info in &worktrees {
+        if info.is_main || info.prunable.is_some() {
+            continue;
+        }
+        let source = common::normalize_path(&info.path);
+        if extra_roots.iter().any(|root| source.starts_with(root)) {
+            continue;
+        }
+        let Some((root, destination)) =
+            target_of(repo, &layout_root, old_root.as_deref(), info, &source)
+        else {
+            continue;
+        };
+
+        let others: Vec<PathBuf> = taken.iter().filter(|path| **path != source).cloned().collect();
+        let destination = import::destination_for(&destination, &others, config.path_collision)?;
+        taken.push(destination.clone());
+
+        let processes = table.inside(&source, &[]);
+        let skip = if source == current_path {
+            Some("current worktree".to_string())
+        } else if let Some(reason) = &info.locked {
+            Some(lock::locked_reason(reason))
+        } else if !processes.is_empty() {
+            Some(rm::in_use_reason(&processes))
+        } else {
+            None
+        };
+        plan.push(PlanItem {
+            name: info
+                .branch
+                .clone()
+                .unwrap_or_else(|| common::display_name(info, &base_dir)),
+            source,
+            destination,
+            old_root: root,
+            skip,
+        });
+    }
+
+    let mut stdout = io::stdout().lock();
+    let targets: Vec<&PlanItem> = plan.iter().filter(|item| item.skip.is_none()).collect();
+    if plan.is_empty() {
+        writeln!(stdout, "All managed worktrees are already in place")?;
+    } else {
+        writeln!(stdout, "Relocating {} worktree(s):", targets.len())?;
+        for item in &plan {
+            writeln!(
+                stdout,
+                "  {}  {} -> {}{}",
+                item.name,
+                item.source.display(),
+                item.destination.display(),
+                item.skip
+                    .as_ref()
+                    .map(|reason| format!("  (skip: {})", reason))
+                    .unwrap_or_default()
+            )?;
+        }
+    }
+    if cmd.dry_run {
+        if cmd.relative_paths {
+            writeln!(stdout, "Would switch worktrees to relative gitdir links")?;
+        }
+        return Ok(());
+    }
+
+    if !targets.is_empty() && !cmd.yes {
+        let prompt = format!("Move {} worktree(s)?", targets.len());
+        if !safety::confirm(&prompt)? {
+            return Err(AppError::user(
+                "relocate cancelled; pass --yes to relocate without confirmation",
+            )
+            .into());
+        }
+    }
+
+    if cmd.relative_paths {
+        // 以降の移動や作成でも相対パスのリンクを書くようにする
+        common::run_git_in(git, repo.main_root(), &["config", "worktree.useRelativePaths", "true"])?;
+    }
+
+    let from_roots: Vec<(PathBuf, PathBuf)> = targets
+        .iter()
+        .filter(|item| item.old_root != layout_root)
+        .map(|item| (item.old_root.clone(), layout_root.clone()))
+        .collect();
+    for item in &targets {
+        if let Some(parent) = item.destination.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        common::run_git_in(
+            git,
+            repo.main_root(),
+            &[
+                "worktree",
+                "move",
+                &item.source.to_string_lossy(),
+                &item.destination.to_string_lossy(),
+            ],
+        )?;
+        // 移動元に残った空のディレクトリを片付ける
+        let _ = cleanup::remove_empty_parents(&CleanupContext {
+            worktree_path: item.source.clone(),
+            admin_dir: None,
+            base_dir: parent_of(&item.old_root),
+        });
+        writeln!(stdout, "Moved '{}' to {}", item.name, item.destination.display())?;
+    }
+
+    // ゴミ箱の復元先も新しい配置に合わせる
+    let mut rebased = Vec::new();
+    for (from, to) in from_roots {
+        if !rebased.contains(&from) {
+            trash::rebase_paths(repo, &from, &to)?;
+            rebased.push(from);
+        }
+    }
+
+    if cmd.relative_paths {
+        common::run_git_in(git, repo.main_root(), &["worktree", "repair", "--relative-paths"])?;
+        writeln!(stdout, "Switched worktrees to relative gitdir links")?;
+    }
+    Ok(())
+}
+
+/// 新しい配置に移すべき worktree なら、移動元の `<旧 base_dir>/<リポジトリ名>` と移動先を返す
+fn target_of(
+    repo: &RepoContext,
+    layout_root: &Path,
+    old_root: Option<&Path>,
+    info: &WorktreeInfo,
+    source: &Path,
+) -> Option<(PathBuf, PathBuf)> {
+    let root = if source.starts_with(layout_root) {
+        layout_root.to_path_buf()
+    } else if let Some(old_root) = old_root.filter(|root| source.starts_with(root)) {
+        old_root.to_path_buf()
+    } else {
+        detect_root(repo, info.branch.as_deref()?, source)?
+    };
+
+    let destination = match info.branch.as_deref() {
+        Some(branch) => {
+            let layout = layout_root.join(pathmap::branch_to_relative_path(branch));
+            // 衝突で連番を付けたものも、そのブランチの正しい配置とみなす
+            if root == layout_root && strip_suffix(source) == layout {
+                return None;
+            }
+            layout
+        }
+        None => {
+            if root == layout_root {
+                return None;
+            }
+            let relative = source.strip_prefix(&root).unwrap_or(source);
+            layout_root.join(relative)
+        }
+    };
+    if destination == source {
+        return None;
+    }
+    Some((root, destination))
+}
+
+/// `<dir>/<リポジトリ名>/<ブランチのパス>` の形をしていれば `<dir>/<リポジトリ名>` を返す
+fn detect_root(repo: &RepoContext, branch: &str, source: &Path) -> Option<PathBuf> {
+    let relative = pathmap::branch_to_relative_path(branch);
+    let expected = Path::new(repo.repo_name()).join(&relative);
+    let path = [source.to_path_buf(), strip_suffix(source)]
+        .into_iter()
+        .find(|path| path.ends_with(&expected))?;
+    let mut root = path.as_path();
+    for _ in relative.components() {
+        root = root.parent()?;
+    }
+    Some(root.to_path_buf())
+}
+
+/// `feature/auth-2` のように衝突回避の連番が付いたパスから連番を除く
+fn strip_suffix(path: &Path) -> PathBuf {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return path.to_path_buf();
+    };
+    match name.rsplit_once('-') {
+        Some((stem, number))
+            if !stem.is_empty()
+                && !number.is_empty()
+                && number.chars().all(|ch| ch.is_ascii_digit()) =>
+        {
+            path.with_file_name(stem)
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+fn parent_of(path: &Path) -> PathBuf {
+    path.parent().unwrap_or(path).to_path_buf()
+}
+
+fn ensure_relative_paths_supported(git: &GitRunner) -> Result<()> {
+    let output = git
+        .run(["version"])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    let version = output.stdout().trim().trim_start_matches("git version ").to_string();
+    if parse_version(&version).is_some_and(|found| found >= RELATIVE_PATHS_SINCE) {
+        return Ok(());
+    }
+    Err(AppError::user(format!(
+        "relative gitdir links require git {}.{} or later (found {})",
+        RELATIVE_PATHS_SINCE.0, RELATIVE_PATHS_SINCE.1, version
+    ))
+    .into())
+}
+
+/// `2.39.5` や `2.48.1.windows.1` から (メジャー, マイナー) を取り出す
+fn parse_version(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_version_reads_major_and_minor() {
+        assert_eq!(parse_version("2.39.5"), Some((2, 39)));
+        assert_eq!(parse_version("2.48.1.windows.1"), Some((2, 48)));
+        assert_eq!(parse_version("unknown"), None);
+    }
+
+    #[test]
+    fn strip_suffix_removes_collision_number_only() {
+        assert_eq!(
+            strip_suffix(Path::new("/w/repo/feature/auth-2")),
+            PathBuf::from("/w/repo/feature/auth")
+        );
+        assert_eq!(
+            strip_suffix(Path::new("/w/repo/feature/auth")),
+            PathBuf::from("/w/repo/feature/auth")
+        );
+        assert_eq!(
+            strip_suffix(Path::new("/w/repo/fix-login")),
+            PathBuf::from("/w/repo/fix-login")
+        );
+    }
+}
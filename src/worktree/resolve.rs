@@ -39,10 +39,11 @@ pub fn resolve_worktree_path(
 
     let worktrees = list_worktrees(git)?;
     let base_dir = common::normalize_path(&config.resolved_base_dir(repo.main_root()));
+    let roots = config.managed_roots(repo.main_root());
     let repo_name = repo.repo_name().to_string();
 
-    let resolved = resolve_path(&worktrees, &base_dir, &repo_name, &target)
-        .ok_or_else(|| worktree_not_found(&target, &worktrees, &base_dir, &repo_name))
+    let resolved = resolve_path(&worktrees, &base_dir, &roots, &repo_name, &target)
+        .ok_or_else(|| worktree_not_found(&target, &worktrees, &base_dir, &roots, &repo_name))
         .map_err(anyhow::Error::from)?;
 
     Ok(common::normalize_path(&resolved))
@@ -55,6 +56,7 @@ fn sanitize_target(target: &str) -> String {
 fn resolve_path(
     worktrees: &[WorktreeInfo],
     base_dir: &Path,
+    roots: &[PathBuf],
     repo_name: &str,
     target: &str,
 ) -> Option<PathBuf> {
@@ -65,7 +67,7 @@ fn resolve_path(
             return Some(path);
         }
 
-        if !common::is_managed(info, base_dir) {
+        if !common::is_managed(info, roots) {
             continue;
         }
 
@@ -85,6 +87,7 @@ fn resolve_path(
     None
 }
 
+#[allow(clippy::collapsible_if)]
 fn matches_main(info: &WorktreeInfo, repo_name: &str, target: &str) -> bool {
     if !info.is_main {
         return false;
@@ -130,15 +133,17 @@ fn matches_directory_name(info: &WorktreeInfo, target: &str) -> bool {
         .unwrap_or(false)
 }
 
+#[allow(clippy::collapsible_if)]
 fn worktree_not_found(
     target: &str,
     worktrees: &[WorktreeInfo],
     base_dir: &Path,
+    roots: &[PathBuf],
     repo_name: &str,
 ) -> AppError {
     let mut available = Vec::new();
     for info in worktrees {
-        if !common::is_managed(info, base_dir) {
+        if !common::is_managed(info, roots) {
             continue;
         }
         available.push(common::display_name(info, base_dir));
@@ -195,14 +200,14 @@ mod tests {
         let base_dir = fixture.base_dir.clone();
         let repo = fixture.repo_name.clone();
 
-        let resolved_main = super::resolve_path(&worktrees, &base_dir, &repo, "@").unwrap();
+        let resolved_main = super::resolve_path(&worktrees, &base_dir, std::slice::from_ref(&base_dir), &repo, "@").unwrap();
         assert_eq!(common::normalize_path(&resolved_main), fixture.main_path);
 
-        let resolved_repo = super::resolve_path(&worktrees, &base_dir, &repo, &repo).unwrap();
+        let resolved_repo = super::resolve_path(&worktrees, &base_dir, std::slice::from_ref(&base_dir), &repo, &repo).unwrap();
         assert_eq!(common::normalize_path(&resolved_repo), fixture.main_path);
 
         let resolved_branch =
-            super::resolve_path(&worktrees, &base_dir, &repo, "feature/auth").unwrap();
+            super::resolve_path(&worktrees, &base_dir, std::slice::from_ref(&base_dir), &repo, "feature/auth").unwrap();
         assert_eq!(common::normalize_path(&resolved_branch), fixture.feature_path);
     }
 
@@ -213,7 +218,7 @@ mod tests {
         let base_dir = fixture.base_dir.clone();
 
         let resolved =
-            super::resolve_path(&worktrees, &base_dir, &fixture.repo_name, &fixture.feature_display)
+            super::resolve_path(&worktrees, &base_dir, std::slice::from_ref(&base_dir), &fixture.repo_name, &fixture.feature_display)
                 .unwrap();
         assert_eq!(common::normalize_path(&resolved), fixture.feature_path);
     }
@@ -221,8 +226,13 @@ mod tests {
     #[test]
     fn worktree_not_found_lists_available_options() {
         let fixture = Fixture::new();
-        let err =
-            super::worktree_not_found("ghost", &fixture.worktrees, &fixture.base_dir, "repo");
+        let err = super::worktree_not_found(
+            "ghost",
+            &fixture.worktrees,
+            &fixture.base_dir,
+            std::slice::from_ref(&fixture.base_dir),
+            "repo",
+        );
         let message = format!("{err}");
         assert!(
             message.contains("Available worktrees"),
@@ -1,6 +1,6 @@
 use std::ffi::OsString;
-use std::io::{self, Write};
-use std::path::Path;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 
@@ -10,7 +10,9 @@ use crate::error::AppError;
 use crate::git::rev::RepoContext;
 use crate::git::runner::{GitError, GitRunner};
 use crate::git::worktree::{WorktreeInfo, list_worktrees};
-use crate::worktree::common;
+use crate::worktree::cleanup::{self, CleanupContext};
+use crate::worktree::process::{ProcessInfo, ProcessTable};
+use crate::worktree::{common, list, lock, metadata, process, resolve, safety, trash};
 
 pub fn run(
     repo: &RepoContext,
@@ -18,20 +20,125 @@ pub fn run(
     config: &Config,
     cmd: &RmCommand,
 ) -> Result<()> {
-    let target = cmd
-        .target
-        .as_ref()
+    if let Some(group) = &cmd.group {
+        return remove_group(repo, git, config, group, cmd);
+    }
+
+    let targets: Vec<String> = cmd
+        .targets
+        .iter()
         .map(|s| s.trim())
         .filter(|s| !s.is_empty())
-        .ok_or_else(|| AppError::user("worktree name is required"))
-        .map_err(anyhow::Error::from)?
-        .to_string();
+        .map(str::to_string)
+        .collect();
+    if cmd.here || targets == ["."] {
+        return remove_here(repo, git, config, cmd);
+    }
+    match targets.as_slice() {
+        [] => Err(AppError::user("worktree name is required").into()),
+        [target] if !is_pattern(target) => remove_single(repo, git, config, cmd, target),
+        _ => remove_many(repo, git, config, cmd, &targets),
+    }
+}
+
+/// 現在の worktree を削除し、移動先のディレクトリを標準出力へ書き出す。
+/// シェル統合はそのパスへ移動する (`gwe cd` と同じ仕組み)。
+/// 標準出力は移動先だけにするため、進捗は標準エラーへ出す。
+fn remove_here(repo: &RepoContext, git: &GitRunner, config: &Config, cmd: &RmCommand) -> Result<()> {
+    if repo.is_main_worktree() {
+        return Err(AppError::user("cannot remove the main worktree").into());
+    }
+
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
+    let current_path = common::normalize_path(repo.worktree_root());
+    let info = worktrees
+        .iter()
+        .find(|info| common::normalize_path(&info.path) == current_path)
+        .filter(|info| common::is_managed(info, &roots))
+        .ok_or_else(|| {
+            AppError::user(format!(
+                "the current worktree is not managed by gwe: {}",
+                current_path.display()
+            ))
+        })?;
+    let name = common::display_name(info, &base_dir);
+
+    let destination = fallback_destination(repo, git, config, &current_path);
+
+    let label = format!("'{}'", name);
+    lock::ensure_unlocked(info, &label, cmd.force)?;
+    let table = ProcessTable::scan();
+    if !cmd.kill {
+        ensure_not_busy(&table, info, &label)?;
+    }
+
+    let targets = [(info, name.clone())];
+    let losses = ensure_no_loss(git, config, &worktrees, &targets, cmd, &label)?;
+    let remote = prepare_remote(git, config, cmd, info)?;
+
+    // 削除するディレクトリの中に居続けないよう、gwe 自身も移動しておく
+    std::env::set_current_dir(&destination)?;
+
+    let mut stderr = io::stderr().lock();
+    if cmd.kill {
+        stop_processes(&mut stderr, &label, &table.inside(&info.path, &[]))?;
+    }
+    if losses[0] {
+        move_to_trash(&mut stderr, repo, git, config, info)?;
+    }
+    remove_one(&mut stderr, git, &base_dir, info, &name, cmd.with_branch)?;
+    if let Some(remote) = &remote {
+        delete_remote(&mut stderr, git, remote)?;
+    }
+    drop(stderr);
+
+    writeln!(io::stdout().lock(), "{}", destination.display())?;
+    Ok(())
+}
+
+/// `gwe rm --here` の後に移動するディレクトリ (`gwe.rm.fallback` かメイン worktree)
+fn fallback_destination(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    removing: &Path,
+) -> PathBuf {
+    config
+        .rm_fallback
+        .as_ref()
+        .and_then(|fallback| {
+            let path = Path::new(fallback);
+            if path.is_absolute() && path.is_dir() {
+                return Some(common::normalize_path(path));
+            }
+            resolve::resolve_worktree_path(repo, git, config, Some(fallback.clone())).ok()
+        })
+        .filter(|path| !path.starts_with(removing))
+        .unwrap_or_else(|| common::normalize_path(repo.main_root()))
+}
+
+/// 複数削除の各対象の結果
+enum Outcome {
+    Removed,
+    Skipped(String),
+    Failed(String),
+}
 
+fn remove_single(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &RmCommand,
+    target: &str,
+) -> Result<()> {
     let worktrees = list_worktrees(git)?;
     let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
 
-    let target_info =
-        find_target_worktree(&worktrees, &base_dir, &target).map_err(anyhow::Error::from)?;
+    let target_info = find_target_worktree(&worktrees, &base_dir, &roots, target)
+        .map_err(anyhow::Error::from)?;
 
     let current_path = common::normalize_path(repo.worktree_root());
     let target_path = common::normalize_path(&target_info.path);
@@ -45,30 +152,482 @@ pub fn run(
         .into());
     }
 
-    let display_path = common::normalize_path(&target_info.path);
-    remove_worktree(git, &target_info.path, true).map_err(anyhow::Error::from)?;
+    let label = format!("'{}'", target);
+    lock::ensure_unlocked(target_info, &label, cmd.force)?;
+    let table = ProcessTable::scan();
+    if !cmd.kill {
+        ensure_not_busy(&table, target_info, &label)?;
+    }
+
+    let targets = [(target_info, target.to_string())];
+    let losses = ensure_no_loss(git, config, &worktrees, &targets, cmd, &label)?;
+    let remote = prepare_remote(git, config, cmd, target_info)?;
+
+    let mut stdout = io::stdout().lock();
+    if cmd.kill {
+        stop_processes(&mut stdout, &label, &table.inside(&target_info.path, &[]))?;
+    }
+    if losses[0] {
+        move_to_trash(&mut stdout, repo, git, config, target_info)?;
+    }
+    remove_one(&mut stdout, git, &base_dir, target_info, target, cmd.with_branch)?;
+    if let Some(remote) = &remote {
+        delete_remote(&mut stdout, git, remote)?;
+    }
+    Ok(())
+}
+
+/// 複数の対象・パターンに一致する worktree を削除する。
+/// 個々の失敗では止まらず、最後に結果をまとめて表示する。
+fn remove_many(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &RmCommand,
+    targets: &[String],
+) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let roots = config.managed_roots(repo.main_root());
+    let current_path = common::normalize_path(repo.worktree_root());
+
+    let mut outcomes: Vec<(String, Outcome)> = Vec::new();
+    let mut selected: Vec<(&WorktreeInfo, String)> = Vec::new();
+    for target in targets {
+        if is_pattern(target) {
+            let matches = match_pattern(&worktrees, &base_dir, &roots, target)?;
+            if matches.is_empty() {
+                let reason = format!("no worktree matches '{}'", target);
+                outcomes.push((target.clone(), Outcome::Failed(reason)));
+            }
+            for info in matches {
+                let name = info
+                    .branch
+                    .clone()
+                    .unwrap_or_else(|| common::display_name(info, &base_dir));
+                select(&mut selected, info, name);
+            }
+        } else {
+            match find_target_worktree(&worktrees, &base_dir, &roots, target) {
+                Ok(info) => select(&mut selected, info, target.clone()),
+                Err(err) => {
+                    let message = err.to_string();
+                    let reason = message.lines().next().unwrap_or_default().to_string();
+                    outcomes.push((target.clone(), Outcome::Failed(reason)));
+                }
+            }
+        }
+    }
 
     let mut stdout = io::stdout().lock();
+    if !selected.is_empty() {
+        writeln!(stdout, "Matched {} worktree(s):", selected.len())?;
+        for (info, name) in &selected {
+            writeln!(stdout, "  {}  {}", name, common::normalize_path(&info.path).display())?;
+        }
+        stdout.flush()?;
+
+        if !cmd.yes
+            && io::stdin().is_terminal()
+            && !safety::confirm(&format!("Remove {} worktree(s)?", selected.len()))?
+        {
+            return Err(AppError::user("rm cancelled").into());
+        }
+    }
+
+    let table = ProcessTable::scan();
+    for (info, name) in &selected {
+        let outcome = if common::normalize_path(&info.path) == current_path {
+            Outcome::Skipped("current worktree".to_string())
+        } else {
+            // 1 つだけ削除する場合と同じ検査を対象ごとに行う
+            let removal = (|| -> Result<()> {
+                let label = format!("'{}'", name);
+                lock::ensure_unlocked(info, &label, cmd.force)?;
+                if !cmd.kill {
+                    ensure_not_busy(&table, info, &label)?;
+                }
+                let losses =
+                    ensure_no_loss(git, config, &worktrees, &[(*info, name.clone())], cmd, &label)?;
+                let remote = prepare_remote(git, config, cmd, info)?;
+                if cmd.kill {
+                    stop_processes(&mut stdout, &label, &table.inside(&info.path, &[]))?;
+                }
+                if losses[0] {
+                    move_to_trash(&mut stdout, repo, git, config, info)?;
+                }
+                remove_one(&mut stdout, git, &base_dir, info, name, cmd.with_branch)?;
+                if let Some(remote) = &remote {
+                    delete_remote(&mut stdout, git, remote)?;
+                }
+                Ok(())
+            })();
+            match removal {
+                Ok(()) => Outcome::Removed,
+                Err(err) => Outcome::Failed(err.to_string()),
+            }
+        };
+        outcomes.push((name.clone(), outcome));
+    }
+
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+    let removed = count(|o| matches!(o, Outcome::Removed));
+    let skipped = count(|o| matches!(o, Outcome::Skipped(_)));
+    let failed = count(|o| matches!(o, Outcome::Failed(_)));
+
     writeln!(
         stdout,
+        "Summary: {} removed, {} skipped, {} failed",
+        removed, skipped, failed
+    )?;
+    for (name, outcome) in &outcomes {
+        match outcome {
+            Outcome::Removed => writeln!(stdout, "  ✓ {}  removed", name)?,
+            Outcome::Skipped(reason) => writeln!(stdout, "  - {}  skipped ({})", name, reason)?,
+            Outcome::Failed(reason) => {
+                // 2 行目以降 (失われる作業の一覧や対処方法) は字下げして続ける
+                let mut lines = reason.lines();
+                writeln!(stdout, "  ✗ {}  failed ({})", name, lines.next().unwrap_or_default())?;
+                for line in lines {
+                    writeln!(stdout, "      {}", line)?;
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(AppError::user(format!(
+            "{} of {} target(s) could not be removed",
+            failed,
+            outcomes.len()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// 同じ worktree を二重に選ばないように追加する
+fn select<'a>(selected: &mut Vec<(&'a WorktreeInfo, String)>, info: &'a WorktreeInfo, name: String) {
+    let path = common::normalize_path(&info.path);
+    if !selected
+        .iter()
+        .any(|(other, _)| common::normalize_path(&other.path) == path)
+    {
+        selected.push((info, name));
+    }
+}
+
+fn is_pattern(target: &str) -> bool {
+    target.contains(['*', '?', '['])
+}
+
+/// ブランチ名・ディレクトリ名・表示名のいずれかが glob パターンに一致する管理下の worktree
+fn match_pattern<'a>(
+    worktrees: &'a [WorktreeInfo],
+    base_dir: &Path,
+    roots: &[PathBuf],
+    pattern: &str,
+) -> Result<Vec<&'a WorktreeInfo>> {
+    let glob = glob::Pattern::new(pattern)
+        .map_err(|err| AppError::user(format!("invalid pattern '{}': {}", pattern, err)))?;
+    Ok(worktrees
+        .iter()
+        .filter(|info| !info.is_main && common::is_managed(info, roots))
+        .filter(|info| {
+            info.branch.as_deref().is_some_and(|branch| glob.matches(branch))
+                || info
+                    .path
+                    .file_name()
+                    .is_some_and(|name| glob.matches(&name.to_string_lossy()))
+                || glob.matches(&common::display_name(info, base_dir))
+        })
+        .collect())
+}
+
+/// `-x` などで同時に作成したグループの worktree をまとめて削除する
+fn remove_group(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    group: &str,
+    cmd: &RmCommand,
+) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let members = metadata::group_members(&worktrees, group);
+    if members.is_empty() {
+        return Err(AppError::user(format!("group '{}' not found", group)).into());
+    }
+
+    let current_path = common::normalize_path(repo.worktree_root());
+    if let Some((info, _)) = members
+        .iter()
+        .find(|(info, _)| common::normalize_path(&info.path) == current_path)
+    {
+        return Err(AppError::user(format!(
+            "cannot remove group '{}': it contains the current worktree {}",
+            group,
+            info.path.display()
+        ))
+        .into());
+    }
+
+    let base_dir = config.resolved_base_dir(repo.main_root());
+    let targets: Vec<(&WorktreeInfo, String)> = members
+        .iter()
+        .map(|(info, _)| (*info, common::display_name(info, &base_dir)))
+        .collect();
+    for (info, name) in &targets {
+        lock::ensure_unlocked(info, &format!("'{}'", name), cmd.force)?;
+    }
+    let table = ProcessTable::scan();
+    if !cmd.kill {
+        for (info, name) in &targets {
+            ensure_not_busy(&table, info, &format!("'{}'", name))?;
+        }
+    }
+    let losses =
+        ensure_no_loss(git, config, &worktrees, &targets, cmd, &format!("group '{}'", group))?;
+    let remotes = targets
+        .iter()
+        .map(|(info, _)| prepare_remote(git, config, cmd, info))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut stdout = io::stdout().lock();
+    for (((info, name), lose), remote) in targets.iter().zip(losses).zip(&remotes) {
+        if cmd.kill {
+            stop_processes(&mut stdout, &format!("'{}'", name), &table.inside(&info.path, &[]))?;
+        }
+        if lose {
+            move_to_trash(&mut stdout, repo, git, config, info)?;
+        }
+        remove_one(&mut stdout, git, &base_dir, info, name, cmd.with_branch)?;
+        if let Some(remote) = remote {
+            delete_remote(&mut stdout, git, remote)?;
+        }
+    }
+    writeln!(stdout, "Removed group '{}' ({} worktrees)", group, members.len())?;
+    Ok(())
+}
+
+/// worktree 内で動いているプロセスがあれば、一覧を示して削除を拒否する
+fn ensure_not_busy(table: &ProcessTable, info: &WorktreeInfo, label: &str) -> Result<()> {
+    let processes = table.inside(&info.path, &[]);
+    if processes.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::user(process::busy_message(label, &processes)).into())
+    }
+}
+
+/// 複数削除の結果や prune の計画に表示する、使用中のプロセス一覧
+pub fn in_use_reason(processes: &[ProcessInfo]) -> String {
+    let list: Vec<String> = processes.iter().map(ProcessInfo::describe).collect();
+    format!("in use by {}", list.join(", "))
+}
+
+/// `--kill` で worktree 内のプロセスを終了させる
+pub fn stop_processes<W: Write>(writer: &mut W, label: &str, processes: &[ProcessInfo]) -> Result<()> {
+    if processes.is_empty() {
+        return Ok(());
+    }
+    let list: Vec<String> = processes.iter().map(ProcessInfo::describe).collect();
+    writeln!(writer, "Stopping processes in {}: {}", label, list.join(", "))?;
+    process::terminate(processes)
+}
+
+/// 削除で失われる作業があれば一覧を示し、`--force` か確認がない限り拒否する。
+/// 対象ごとに失われる作業があるか (ゴミ箱へ退避すべきか) を返す。
+fn ensure_no_loss(
+    git: &GitRunner,
+    config: &Config,
+    worktrees: &[WorktreeInfo],
+    targets: &[(&WorktreeInfo, String)],
+    cmd: &RmCommand,
+    label: &str,
+) -> Result<Vec<bool>> {
+    let mut lines = Vec::new();
+    let mut losses = Vec::new();
+    for (info, name) in targets {
+        let work = safety::unsaved_work(git, config, worktrees, info, cmd.with_branch)?;
+        losses.push(!work.is_empty());
+        if targets.len() == 1 {
+            lines.extend(work.describe());
+        } else {
+            lines.extend(work.describe().into_iter().map(|line| format!("{}: {}", name, line)));
+        }
+    }
+
+    if safety::confirm_loss(config, cmd.force > 0, label, &lines)? {
+        Ok(losses)
+    } else {
+        Err(AppError::user(safety::refusal_message(label, &lines)).into())
+    }
+}
+
+/// `--remote` で削除するリモートブランチ
+pub struct RemoteBranch {
+    pub remote: String,
+    /// リモート側のブランチ (`refs/heads/feature/x`)
+    pub reference: String,
+    /// 追跡ブランチ (`origin/feature/x`)
+    pub upstream: String,
+}
+
+/// `--remote` で削除するブランチの追跡先を調べる。
+/// リモートにローカルにないコミットがあれば、`--force` でない限り確認を求める。
+fn prepare_remote(
+    git: &GitRunner,
+    config: &Config,
+    cmd: &RmCommand,
+    info: &WorktreeInfo,
+) -> Result<Option<RemoteBranch>> {
+    let Some(branch) = info.branch.as_deref().filter(|_| cmd.remote) else {
+        return Ok(None);
+    };
+    let Some(remote) = tracked_remote(git, branch)? else {
+        return Ok(None);
+    };
+
+    let missing = remote_only_commits(git, branch)?;
+    if missing > 0 && cmd.force == 0 && !config.rm_force {
+        let prompt = format!(
+            "'{}' has {} commit(s) not present locally. Delete it anyway?",
+            remote.upstream, missing
+        );
+        if !safety::confirm(&prompt)? {
+            return Err(AppError::user(format!(
+                "refusing to delete remote branch '{}': it has {} commit(s) not present locally\nPull them first, or pass --force",
+                remote.upstream, missing
+            ))
+            .into());
+        }
+    }
+    Ok(Some(remote))
+}
+
+/// ブランチが追跡しているリモートブランチを調べ、最新の状態を取得する。
+/// 追跡先がない、またはリモートから既に消えていれば None を返す。
+pub fn tracked_remote(git: &GitRunner, branch: &str) -> Result<Option<RemoteBranch>> {
+    let main_root = git.repo().main_root();
+    let Some(upstream) = list::upstream_of(git, main_root, branch)? else {
+        return Ok(None);
+    };
+
+    let local_ref = format!("refs/heads/{}", branch);
+    let output = git
+        .run_in(
+            main_root,
+            [
+                "for-each-ref",
+                "--format=%(upstream:remotename)%00%(upstream:remoteref)",
+                local_ref.as_str(),
+            ],
+        )
+        .map_err(|err| AppError::git(err.to_string()))?;
+    let (remote, reference) = match output.stdout().trim().split_once('\0') {
+        // `branch.<name>.remote = .` のようにローカルブランチを追跡している場合は対象外
+        Some((remote, reference)) if !remote.is_empty() && remote != "." => {
+            (remote.to_string(), reference.to_string())
+        }
+        _ => return Ok(None),
+    };
+
+    // マージ時に自動削除されるなど、リモートから既に消えていれば何もしない
+    let listed = git
+        .run_in(main_root, ["ls-remote", "--heads", remote.as_str(), reference.as_str()])
+        .map_err(|err| AppError::git(err.to_string()))?;
+    if listed.stdout().trim().is_empty() {
+        return Ok(None);
+    }
+    git.run_in(main_root, ["fetch", "--quiet", remote.as_str(), reference.as_str()])
+        .map_err(|err| AppError::git(err.to_string()))?;
+
+    Ok(Some(RemoteBranch {
+        remote,
+        reference,
+        upstream,
+    }))
+}
+
+/// 追跡先にあって、ローカルのどのブランチにも含まれていないコミットの数
+pub fn remote_only_commits(git: &GitRunner, branch: &str) -> Result<usize> {
+    let tracking = format!("{}@{{u}}", branch);
+    let output = git
+        .run_in(
+            git.repo().main_root(),
+            ["rev-list", "--count", tracking.as_str(), "--not", "--branches"],
+        )
+        .map_err(|err| AppError::git(err.to_string()))?;
+    Ok(output.stdout().trim().parse().unwrap_or(0))
+}
+
+fn delete_remote<W: Write>(writer: &mut W, git: &GitRunner, remote: &RemoteBranch) -> Result<()> {
+    git.run_in(
+        git.repo().main_root(),
+        ["push", "--quiet", remote.remote.as_str(), "--delete", remote.reference.as_str()],
+    )
+    .map_err(common::git_error)?;
+    writeln!(writer, "Removed remote branch '{}'", remote.upstream)?;
+    Ok(())
+}
+
+/// 失われる作業をゴミ箱へ退避する。`gwe restore <id>` で元に戻せる
+pub fn move_to_trash<W: Write>(
+    writer: &mut W,
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    info: &WorktreeInfo,
+) -> Result<()> {
+    if !info.path.exists() {
+        return Ok(());
+    }
+    let entry = trash::save(repo, git, config, info)?;
+    writeln!(
+        writer,
+        "Saved to trash as {} (restore with 'gwe restore {}')",
+        entry.id, entry.id
+    )?;
+    Ok(())
+}
+
+/// worktree (と `with_branch` ならブランチ) を削除し、登録された後始末を実行する
+pub fn remove_one<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    base_dir: &Path,
+    info: &WorktreeInfo,
+    name: &str,
+    with_branch: bool,
+) -> Result<()> {
+    let display_path = common::normalize_path(&info.path);
+    let cleanup = CleanupContext::capture(&info.path, base_dir);
+    // ロックされた worktree は呼び出し側で -ff を確認済みなので、--force を重ねて削除する
+    let force = if info.locked.is_some() { 2 } else { 1 };
+    remove_worktree(git, &info.path, force).map_err(anyhow::Error::from)?;
+    cleanup::run_all(&cleanup);
+
+    writeln!(
+        writer,
         "Removed worktree '{}' at {}",
-        target,
+        name,
         display_path.display()
     )?;
 
-    if cmd.with_branch {
-        if let Some(branch) = &target_info.branch {
-            remove_branch(git, branch, true).map_err(anyhow::Error::from)?;
-            writeln!(stdout, "Removed branch '{}'", branch)?;
-        }
+    if with_branch
+        && let Some(branch) = &info.branch
+    {
+        remove_branch(git, branch, true).map_err(anyhow::Error::from)?;
+        writeln!(writer, "Removed branch '{}'", branch)?;
     }
 
     Ok(())
 }
 
-fn find_target_worktree<'a>(
+pub fn find_target_worktree<'a>(
     worktrees: &'a [WorktreeInfo],
     base_dir: &Path,
+    roots: &[PathBuf],
     target: &str,
 ) -> std::result::Result<&'a WorktreeInfo, AppError> {
     let mut available = Vec::new();
@@ -78,7 +637,7 @@ fn find_target_worktree<'a>(
         if info.is_main {
             continue;
         }
-        if !common::is_managed(info, base_dir) {
+        if !common::is_managed(info, roots) {
             continue;
         }
 
@@ -113,16 +672,17 @@ fn find_target_worktree<'a>(
     Err(worktree_not_found(target, available))
 }
 
-fn remove_worktree(git: &GitRunner, path: &Path, force: bool) -> std::result::Result<(), AppError> {
+fn remove_worktree(git: &GitRunner, path: &Path, force: u8) -> std::result::Result<(), AppError> {
     let mut args: Vec<OsString> = Vec::new();
     args.push("worktree".into());
     args.push("remove".into());
-    if force {
+    for _ in 0..force {
         args.push("--force".into());
     }
     args.push(path.to_string_lossy().into_owned().into());
 
-    match git.run(args) {
+    // 削除対象の中から git を実行しないよう、メイン worktree で実行する
+    match git.run_in(git.repo().main_root(), args) {
         Ok(_) => Ok(()),
         Err(GitError::CommandFailed { stderr, .. }) => {
             let message = stderr.trim();
@@ -141,7 +701,7 @@ fn remove_worktree(git: &GitRunner, path: &Path, force: bool) -> std::result::Re
 
 fn remove_branch(git: &GitRunner, branch: &str, force: bool) -> std::result::Result<(), AppError> {
     let flag = if force { "-D" } else { "-d" };
-    match git.run(["branch", flag, branch]) {
+    match git.run_in(git.repo().main_root(), ["branch", flag, branch]) {
         Ok(_) => Ok(()),
         Err(GitError::CommandFailed { stderr, .. }) => {
             let message = stderr.trim();
@@ -0,0 +1,218 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+use crate::worktree::common;
+
+/// worktree を削除したときに失われる作業
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UnsavedWork {
+    /// 未コミットの変更があるファイル数
+    pub uncommitted: usize,
+    /// 未追跡ファイル数
+    pub untracked: usize,
+    /// ベースブランチに取り込まれていないコミット数
+    pub unmerged: usize,
+    /// どのリモートにも存在しないコミット数
+    pub unpushed: usize,
+    /// 比較に使ったベースブランチ
+    pub base: Option<String>,
+}
+
+impl UnsavedWork {
+    pub fn is_empty(&self) -> bool {
+        self.uncommitted == 0 && self.untracked == 0 && self.unmerged == 0 && self.unpushed == 0
+    }
+
+    /// 失われる作業を 1 項目 1 行で返す
+    pub fn describe(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.uncommitted > 0 {
+            lines.push(format!("{} file(s) with uncommitted changes", self.uncommitted));
+        }
+        if self.untracked > 0 {
+            lines.push(format!("{} untracked file(s)", self.untracked));
+        }
+        if self.unmerged > 0 {
+            match &self.base {
+                Some(base) => lines.push(format!(
+                    "{} commit(s) not merged into '{}'",
+                    self.unmerged, base
+                )),
+                None => lines.push(format!(
+                    "{} commit(s) not reachable from any branch",
+                    self.unmerged
+                )),
+            }
+        }
+        if self.unpushed > 0 {
+            lines.push(format!("{} commit(s) not pushed to any remote", self.unpushed));
+        }
+        lines
+    }
+}
+
+/// worktree (と `with_branch` のときはブランチ) を削除すると失われる作業を調べる。
+/// ブランチを残す場合、コミットは失われないので確認しない。
+pub fn unsaved_work(
+    git: &GitRunner,
+    config: &Config,
+    worktrees: &[WorktreeInfo],
+    info: &WorktreeInfo,
+    with_branch: bool,
+) -> Result<UnsavedWork> {
+    let mut work = UnsavedWork::default();
+    let path = common::normalize_path(&info.path);
+    if !path.exists() {
+        // ディレクトリが既にない worktree では失うものがない
+        return Ok(work);
+    }
+
+    let status = common::run_git_in(git, &path, &["status", "--porcelain", "-z"])?;
+    let mut entries = status.split('\0').filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        if entry.starts_with("??") {
+            work.untracked += 1;
+            continue;
+        }
+        work.uncommitted += 1;
+        // リネーム・コピーは元のパスが次のエントリとして続く
+        if entry.starts_with(['R', 'C']) {
+            entries.next();
+        }
+    }
+
+    if info.is_detached {
+        // detached HEAD のコミットはどのブランチからも辿れなければ失われる
+        work.unmerged = count_commits(git, &path, &["--branches", "--remotes", "--tags"])?;
+        return Ok(work);
+    }
+
+    let Some(branch) = info.branch.as_deref().filter(|_| with_branch) else {
+        return Ok(work);
+    };
+
+    if let Some(base) = base_branch(config, worktrees).filter(|base| base != branch) {
+        let base_ref = format!("refs/heads/{}", base);
+        if git.run(["rev-parse", "--verify", "--quiet", base_ref.as_str()]).is_ok() {
+            work.unmerged = count_commits(git, &path, &[base_ref.as_str()])?;
+            work.base = Some(base);
+        }
+    }
+
+    if has_remotes(git) {
+        work.unpushed = count_commits(git, &path, &["--remotes"])?;
+    }
+
+    Ok(work)
+}
+
+/// 失われる作業がある場合に削除するか判断する。
+/// `--force` か `gwe.rm.force=true` なら確認しない。端末であれば確認を求め、
+/// そうでなければ拒否する。
+pub fn confirm_loss(config: &Config, force: bool, target: &str, lines: &[String]) -> Result<bool> {
+    if force || config.rm_force || lines.is_empty() {
+        return Ok(true);
+    }
+
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+
+    let mut stderr = io::stderr().lock();
+    writeln!(stderr, "Removing {} would lose:", target)?;
+    for line in lines {
+        writeln!(stderr, "  - {}", line)?;
+    }
+    drop(stderr);
+    confirm("Remove anyway?")
+}
+
+/// 端末で y/N の確認を求める。端末でなければ常に false を返す
+pub fn confirm(prompt: &str) -> Result<bool> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return Ok(false);
+    }
+
+    let mut stderr = io::stderr().lock();
+    write!(stderr, "{} [y/N] ", prompt)?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// 削除を拒否したときのエラーメッセージ
+pub fn refusal_message(target: &str, lines: &[String]) -> String {
+    let mut message = format!("refusing to remove {}; it would lose:", target);
+    for line in lines {
+        message.push_str("\n  - ");
+        message.push_str(line);
+    }
+    message.push_str("\nCommit or push the work first, or pass --force");
+    message
+}
+
+/// 未マージの判定に使うブランチ (`gwe.defaultBranch` かメイン worktree のブランチ)
+pub fn base_branch(config: &Config, worktrees: &[WorktreeInfo]) -> Option<String> {
+    config.default_branch.clone().or_else(|| {
+        worktrees
+            .iter()
+            .find(|info| info.is_main)
+            .and_then(|info| info.branch.clone())
+    })
+}
+
+/// HEAD から辿れて `exclude` のどれからも辿れないコミット数
+fn count_commits(git: &GitRunner, dir: &std::path::Path, exclude: &[&str]) -> Result<usize> {
+    let mut args = vec!["rev-list", "--count", "HEAD", "--not"];
+    args.extend_from_slice(exclude);
+    let output = common::run_git_in(git, dir, &args)?;
+    Ok(output.trim().parse().unwrap_or(0))
+}
+
+fn has_remotes(git: &GitRunner) -> bool {
+    git.run(["remote"])
+        .map(|output| !output.stdout().trim().is_empty())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_lists_each_kind_of_loss() {
+        let work = UnsavedWork {
+            uncommitted: 2,
+            untracked: 1,
+            unmerged: 3,
+            unpushed: 3,
+            base: Some("main".to_string()),
+        };
+        assert_eq!(
+            work.describe(),
+            vec![
+                "2 file(s) with uncommitted changes",
+                "1 untracked file(s)",
+                "3 commit(s) not merged into 'main'",
+                "3 commit(s) not pushed to any remote",
+            ]
+        );
+        assert!(UnsavedWork::default().describe().is_empty());
+        assert!(UnsavedWork::default().is_empty());
+    }
+
+    #[test]
+    fn refusal_message_suggests_force() {
+        let message = refusal_message("'feature/x'", &["1 untracked file(s)".to_string()]);
+        assert!(message.starts_with("refusing to remove 'feature/x'"));
+        assert!(message.contains("  - 1 untracked file(s)"));
+        assert!(message.ends_with("pass --force"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::error::AppError;
+use crate::git::runner::{GitError, GitRunner};
+use crate::worktree::common;
+
+/// `source` の未コミット変更 (ステージ済み・未ステージ・未追跡) を `target` へ移動する。
+/// 適用に失敗した場合は `source` を元の状態に戻し、`target` は競合状態のまま残す。
+pub fn carry<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    source: &Path,
+    target: &Path,
+    label: &str,
+) -> Result<()> {
+    let before = stash_top(git, source)?;
+    let message = format!("gwe: carry to {}", label);
+    common::run_git_in(
+        git,
+        source,
+        &["stash", "push", "--include-untracked", "--message", message.as_str()],
+    )?;
+    let after = stash_top(git, source)?;
+
+    let stash = match after {
+        Some(sha) if before.as_deref() != Some(sha.as_str()) => sha,
+        _ => {
+            writeln!(writer, "No local changes to carry")?;
+            return Ok(());
+        }
+    };
+
+    writeln!(
+        writer,
+        "Carrying uncommitted changes from {}",
+        source.display()
+    )?;
+
+    if let Err(err) = apply_in(git, target, &stash) {
+        // 元の worktree に変更を戻す。HEAD は変わっていないので競合しない
+        let restored = common::run_git_in(git, source, &["stash", "pop", "--index", "--quiet"]).is_ok();
+        let kept = if restored {
+            format!("The changes were restored in {}.", source.display())
+        } else {
+            format!("The changes are kept in stash {}.", short(&stash))
+        };
+        return Err(AppError::git(format!("{}\n{}", err, kept)).into());
+    }
+
+    if stash_top(git, source)?.as_deref() == Some(stash.as_str()) {
+        common::run_git_in(git, source, &["stash", "drop", "--quiet"])?;
+    }
+    writeln!(writer, "✓ Changes moved to {}", target.display())?;
+    Ok(())
+}
+
+/// 既存の stash を `target` に適用する。stash 自体は削除しない。
+pub fn apply<W: Write>(writer: &mut W, git: &GitRunner, target: &Path, stash: &str) -> Result<()> {
+    writeln!(writer, "Applying stash {}", stash)?;
+    let revision = format!("{}^{{commit}}", stash);
+    let sha = common::run_git_in(git, target, &["rev-parse", "--verify", "--quiet", revision.as_str()])
+        .map_err(|_| AppError::user(format!("stash '{}' not found", stash)))?;
+
+    apply_in(git, target, sha.trim()).map_err(|err| {
+        AppError::git(format!("{}\nThe stash '{}' was left untouched.", err, stash))
+    })?;
+    writeln!(writer, "✓ Stash applied to {}", target.display())?;
+    Ok(())
+}
+
+fn apply_in(git: &GitRunner, target: &Path, stash: &str) -> std::result::Result<(), AppError> {
+    match git.run_in(target, ["stash", "apply", "--index", stash]) {
+        Ok(_) => Ok(()),
+        Err(GitError::CommandFailed { stderr, stdout, .. }) => {
+            let conflicts = conflicted_files(git, target);
+            let detail = if conflicts.is_empty() {
+                let message = stderr.trim();
+                if message.is_empty() {
+                    stdout.trim().to_string()
+                } else {
+                    message.to_string()
+                }
+            } else {
+                format!("Conflicting files:\n  {}", conflicts.join("\n  "))
+            };
+            Err(AppError::git(format!(
+                "failed to apply changes in {}\n{}",
+                target.display(),
+                detail
+            )))
+        }
+        Err(err) => Err(AppError::git(err.to_string())),
+    }
+}
+
+fn conflicted_files(git: &GitRunner, dir: &Path) -> Vec<String> {
+    git.run_in(dir, ["diff", "--name-only", "--diff-filter=U"])
+        .map(|output| {
+            output
+                .stdout()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn stash_top(git: &GitRunner, dir: &Path) -> Result<Option<String>> {
+    match git.run_in(dir, ["rev-parse", "--verify", "--quiet", "refs/stash"]) {
+        Ok(output) => Ok(Some(output.stdout().trim().to_string())),
+        Err(GitError::CommandFailed { .. }) => Ok(None),
+        Err(err) => Err(AppError::git(err.to_string()).into()),
+    }
+}
+
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
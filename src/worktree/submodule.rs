@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::config::types::SubmoduleMode;
+use crate::error::AppError;
+use crate::git::runner::{GitError, GitRunner};
+use crate::worktree::common;
+
+/// 新規 worktree のサブモジュールを初期化する。
+/// メイン worktree が既にクローン済みのサブモジュールはそのオブジェクトからクローンし、
+/// リモートへの fetch を行わない。
+pub fn initialize<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    common_dir: &Path,
+    worktree_path: &Path,
+    mode: SubmoduleMode,
+) -> Result<()> {
+    if mode == SubmoduleMode::None || !worktree_path.join(".gitmodules").is_file() {
+        return Ok(());
+    }
+
+    let recursive = mode == SubmoduleMode::Recursive;
+    writeln!(
+        writer,
+        "\nInitializing submodules{}...",
+        if recursive { " (recursive)" } else { "" }
+    )?;
+    update_submodules(writer, git, worktree_path, &common_dir.join("modules"), recursive)?;
+    writeln!(writer, "✓ Submodules initialized")?;
+    Ok(())
+}
+
+fn update_submodules<W: Write>(
+    writer: &mut W,
+    git: &GitRunner,
+    superproject: &Path,
+    local_modules: &Path,
+    recursive: bool,
+) -> Result<()> {
+    for (name, path) in list_submodules(git, superproject)? {
+        common::run_git_in(git, superproject, &["submodule", "init", "--", path.as_str()])?;
+
+        let local = local_modules.join(&name);
+        if local.is_dir() {
+            writeln!(writer, "  {} (reusing local objects)", path)?;
+            let url = common::run_git_in(
+                git,
+                superproject,
+                &["config", "--get", &format!("submodule.{}.url", name)],
+            )?;
+            let override_url = format!("submodule.{}.url={}", name, local.to_string_lossy());
+            common::run_git_in(
+                git,
+                superproject,
+                &[
+                    "-c",
+                    "protocol.file.allow=always",
+                    "-c",
+                    override_url.as_str(),
+                    "submodule",
+                    "update",
+                    "--",
+                    path.as_str(),
+                ],
+            )?;
+            // クローン元はメイン worktree 側のオブジェクトなので、origin を本来の URL に戻す
+            common::run_git_in(
+                git,
+                &superproject.join(&path),
+                &["remote", "set-url", "origin", url.trim()],
+            )?;
+        } else {
+            writeln!(writer, "  {}", path)?;
+            common::run_git_in(git, superproject, &["submodule", "update", "--", path.as_str()])?;
+        }
+
+        if recursive {
+            update_submodules(
+                writer,
+                git,
+                &superproject.join(&path),
+                &local.join("modules"),
+                recursive,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// `.gitmodules` から (name, path) の一覧を取得する
+fn list_submodules(git: &GitRunner, superproject: &Path) -> Result<Vec<(String, String)>> {
+    if !superproject.join(".gitmodules").is_file() {
+        return Ok(Vec::new());
+    }
+
+    let output = match git.run_in(
+        superproject,
+        [
+            "config",
+            "--file",
+            ".gitmodules",
+            "--get-regexp",
+            r"^submodule\..*\.path$",
+        ],
+    ) {
+        Ok(output) => output,
+        Err(GitError::CommandFailed { .. }) => return Ok(Vec::new()),
+        Err(err) => return Err(AppError::git(err.to_string()).into()),
+    };
+
+    Ok(output
+        .stdout()
+        .lines()
+        .filter_map(|line| {
+            let (key, path) = line.split_once(' ')?;
+            let name = key.strip_prefix("submodule.")?.strip_suffix(".path")?;
+            Some((name.to_string(), path.trim().to_string()))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::rev::RepoContext;
+    use std::fs;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn list_submodules_reads_names_and_paths() {
+        let dir = TempDir::new().expect("temp dir");
+        let status = Command::new("git")
+            .current_dir(dir.path())
+            .args(["init", "-q"])
+            .status()
+            .expect("git init");
+        assert!(status.success());
+        fs::write(
+            dir.path().join(".gitmodules"),
+            "[submodule \"libs/core\"]\n\tpath = vendor/core\n\turl = ../core.git\n",
+        )
+        .expect("write .gitmodules");
+
+        let repo = RepoContext::discover(Some(dir.path().to_path_buf())).expect("repo");
+        let git = GitRunner::new(repo);
+        let modules = list_submodules(&git, dir.path()).expect("list submodules");
+        assert_eq!(
+            modules,
+            vec![("libs/core".to_string(), "vendor/core".to_string())]
+        );
+    }
+}
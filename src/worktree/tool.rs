@@ -3,12 +3,16 @@ use std::process::Command;
 
 use anyhow::{Result, anyhow};
 
-use crate::cli::ToolCommand;
+use crate::cli::{OpenCommand, ToolCommand};
 use crate::config::Config;
 use crate::git::rev::RepoContext;
 use crate::error::AppError;
 use crate::git::runner::GitRunner;
-use crate::worktree::create;
+use crate::git::worktree::list_worktrees;
+use crate::worktree::{common, create, metadata, naming};
+
+/// 分割ペインで同時に起動できるツールの最大数
+const MAX_PANES: usize = 5;
 
 pub fn run_add_command(
     repo: &RepoContext,
@@ -16,7 +20,22 @@ pub fn run_add_command(
     config: &Config,
     cmd: &ToolCommand,
 ) -> Result<()> {
-    if cmd.target.is_none() && cmd.branch.is_none() && cmd.track.is_none() {
+    let cmd = &naming::resolve_branch_name(git, config, cmd, "")?;
+    // ペインを起動しないので作成数に上限はない
+    if let Some(branches) = sibling_branches(config, cmd)? {
+        let paths = create::create_multiple_worktrees(repo, git, config, &branches, &[], cmd)?;
+        for path in paths {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    if cmd.target.is_none()
+        && cmd.branches.is_empty()
+        && cmd.track.is_none()
+        && !cmd.detach
+        && cmd.orphan.is_none()
+    {
         return Err(AppError::user("branch or commit is required").into());
     }
     let target_path = create::ensure_worktree(repo, git, config, cmd)?;
@@ -31,6 +50,14 @@ pub fn run_tool_command(
     cmd: &ToolCommand,
     tool_name: &str,
 ) -> Result<()> {
+    let cmd = &naming::resolve_branch_name(git, config, cmd, tool_name)?;
+    if sibling_branches(config, cmd)?.is_some() {
+        return Err(AppError::user(format!(
+            "'{}' opens a single window; use 'gwe add' to create multiple worktrees",
+            tool_name
+        ))
+        .into());
+    }
     let target_path = create::ensure_worktree(repo, git, config, cmd)?;
     run_tool(tool_name, &target_path, &cmd.args)
 }
@@ -42,9 +69,10 @@ pub fn run_terminal_tool_command(
     cmd: &ToolCommand,
     tool_name: &str,
 ) -> Result<()> {
-    // -x オプションが指定されている場合は複数ワークツリーを作成
-    if let Some(count) = cmd.multiplier {
-        return run_terminal_tool_multi(repo, git, config, cmd, tool_name, count, &cmd.args);
+    let cmd = &naming::resolve_branch_name(git, config, cmd, tool_name)?;
+    // -x または複数の -b が指定されている場合は複数ワークツリーを作成
+    if let Some(branches) = sibling_branches(config, cmd)? {
+        return run_terminal_tool_multi(repo, git, config, cmd, tool_name, &branches);
     }
 
     let target_path = create::ensure_worktree(repo, git, config, cmd)?;
@@ -57,37 +85,63 @@ fn run_terminal_tool_multi(
     config: &Config,
     cmd: &ToolCommand,
     tool_name: &str,
-    count: u8,
-    args: &[String],
+    branches: &[String],
 ) -> Result<()> {
-    use crate::error::AppError;
-
-    // -x は -b (新規ブランチ) と併用必須
-    let base_branch = cmd.branch.as_ref().ok_or_else(|| {
-        AppError::user("-x/--multiplier requires -b/--branch option")
-    })?;
-
-    // -x と target の併用はエラー
-    if cmd.target.is_some() {
-        return Err(AppError::user("-x/--multiplier cannot be used with target worktree").into());
+    // ペインの上限は実際に起動する場合のみ適用する (作成前に検査する)
+    if branches.len() > MAX_PANES {
+        return Err(AppError::user(format!(
+            "at most {} panes can be launched at once (requested {}); use 'gwe add' to create the worktrees without launching",
+            MAX_PANES,
+            branches.len()
+        ))
+        .into());
     }
 
     // 複数ワークツリーを作成
-    let paths = create::create_multiple_worktrees(
-        repo,
-        git,
-        config,
-        base_branch,
-        count,
-        cmd.track.as_deref(),
-    )?;
+    let tools = vec![tool_name.to_string(); branches.len()];
+    let paths = create::create_multiple_worktrees(repo, git, config, branches, &tools, cmd)?;
 
     // ターミナル起動
     println!("\nLaunching {} terminals...", paths.len());
     let tools_with_paths: Vec<(String, PathBuf)> = paths.into_iter()
         .map(|p| (tool_name.to_string(), p))
         .collect();
-    spawn_multiple_tools(&tools_with_paths, args)
+    spawn_multiple_tools(&tools_with_paths, &cmd.args)
+}
+
+/// `-x` または複数の `-b` が指定されている場合に、作成するブランチ名の一覧を返す
+fn sibling_branches(config: &Config, cmd: &ToolCommand) -> Result<Option<Vec<String>>> {
+    if cmd.branches.len() > 1 {
+        if cmd.multiplier.is_some() {
+            return Err(AppError::user(
+                "-x/--multiplier cannot be combined with multiple -b/--branch options",
+            )
+            .into());
+        }
+        if cmd.target.is_some() {
+            return Err(AppError::user(
+                "multiple -b/--branch options cannot be used with target worktree",
+            )
+            .into());
+        }
+        return Ok(Some(cmd.branches.clone()));
+    }
+
+    let Some(count) = cmd.multiplier else {
+        return Ok(None);
+    };
+
+    // -x は -b (新規ブランチ) と併用必須
+    let base_branch = cmd
+        .branch()
+        .ok_or_else(|| AppError::user("-x/--multiplier requires -b/--branch option"))?;
+
+    // -x と target の併用はエラー
+    if cmd.target.is_some() {
+        return Err(AppError::user("-x/--multiplier cannot be used with target worktree").into());
+    }
+
+    Ok(Some(create::sibling_branch_names(config, base_branch, count)))
 }
 
 pub fn run_default_editor(
@@ -127,15 +181,14 @@ pub fn run_multi_cli(
         return Err(anyhow!("No multi-CLI configured. Set it with 'gwe config add gwe.multiCli <NAME>'"));
     }
 
+    let cmd = &naming::resolve_branch_name(git, config, cmd, "cli")?;
     let tools = &config.multi_cli;
     let count = tools.len() as u8;
 
-    let tools_with_paths: Vec<(String, PathBuf)> = if let Some(base_branch) = &cmd.branch {
+    let tools_with_paths: Vec<(String, PathBuf)> = if !cmd.branches.is_empty() {
         // -b が指定されている場合は複数ワークツリーを作成
-        use crate::error::AppError;
-
-        if tools.len() > 5 {
-            return Err(AppError::user(format!("Too many tools in multiCli (max 5 for split panes, found {})", tools.len())).into());
+        if tools.len() > MAX_PANES {
+            return Err(AppError::user(format!("Too many tools in multiCli (max {} for split panes, found {})", MAX_PANES, tools.len())).into());
         }
         
         // target との併用はエラー (-x と同じ)
@@ -143,16 +196,23 @@ pub fn run_multi_cli(
             return Err(AppError::user("'gwe cli' with -b/--branch cannot be used with target worktree").into());
         }
 
-        let paths = create::create_multiple_worktrees(
-            repo,
-            git,
-            config,
-            base_branch,
-            count,
-            cmd.track.as_deref(),
-        )?;
+        // -b を複数指定した場合はツールごとのブランチ名として使う
+        let branches = match cmd.branch() {
+            Some(base_branch) => create::sibling_branch_names(config, base_branch, count),
+            None if cmd.branches.len() == tools.len() => cmd.branches.clone(),
+            None => {
+                return Err(AppError::user(format!(
+                    "'gwe cli' needs one -b/--branch per tool ({} tools, {} branches)",
+                    tools.len(),
+                    cmd.branches.len()
+                ))
+                .into());
+            }
+        };
 
-        tools.iter().zip(paths.into_iter())
+        let paths = create::create_multiple_worktrees(repo, git, config, &branches, tools, cmd)?;
+
+        tools.iter().zip(paths)
             .map(|(t, p)| (t.clone(), p))
             .collect()
     } else {
@@ -167,6 +227,40 @@ pub fn run_multi_cli(
     spawn_multiple_tools(&tools_with_paths, &cmd.args)
 }
 
+/// グループの worktree それぞれで、作成時に記録したツールを分割ペインに起動し直す
+pub fn run_open_group(git: &GitRunner, cmd: &OpenCommand) -> Result<()> {
+    let worktrees = list_worktrees(git)?;
+    let members = metadata::group_members(&worktrees, &cmd.group);
+    if members.is_empty() {
+        return Err(AppError::user(format!("group '{}' not found", cmd.group)).into());
+    }
+    if members.len() > MAX_PANES {
+        return Err(AppError::user(format!(
+            "at most {} panes can be launched at once (group '{}' has {})",
+            MAX_PANES,
+            cmd.group,
+            members.len()
+        ))
+        .into());
+    }
+
+    let tools_with_paths = members
+        .into_iter()
+        .map(|(info, meta)| {
+            let tool = cmd.tool.clone().or(meta.tool).ok_or_else(|| {
+                AppError::user(format!(
+                    "no tool recorded for {}; pass --tool",
+                    info.path.display()
+                ))
+            })?;
+            Ok((tool, common::normalize_path(&info.path)))
+        })
+        .collect::<Result<Vec<(String, PathBuf)>>>()?;
+
+    println!("Launching {} tools in split panes...", tools_with_paths.len());
+    spawn_multiple_tools(&tools_with_paths, &cmd.args)
+}
+
 fn is_terminal_tool(name: &str) -> bool {
     matches!(name, "claude" | "codex" | "gemini")
 }
@@ -287,6 +381,7 @@ fn spawn_terminal(tool: &str, path: &Path, args: &[String]) -> Result<()> {
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
+        let _ = (tool, path, args);
         Err(anyhow!("Terminal spawning is only supported on macOS and Windows for now"))
     }
 }
@@ -359,6 +454,7 @@ fn spawn_multiple_tools(tools_with_paths: &[(String, PathBuf)], args: &[String])
 
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
+        let _ = (tools_with_paths, args);
         Err(anyhow!("Multi-terminal spawning is only supported on macOS and Windows"))
     }
 }
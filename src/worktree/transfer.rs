@@ -0,0 +1,230 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::cli::TransferCommand;
+use crate::config::Config;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::worktree::{common, resolve};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    Move,
+    Copy,
+}
+
+/// 転送したファイルの一覧
+#[derive(Debug, Default)]
+pub struct TransferSummary {
+    pub tracked: Vec<String>,
+    pub untracked: Vec<String>,
+}
+
+impl TransferSummary {
+    pub fn is_empty(&self) -> bool {
+        self.tracked.is_empty() && self.untracked.is_empty()
+    }
+}
+
+pub fn run(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    cmd: &TransferCommand,
+) -> Result<()> {
+    let from = resolve::resolve_worktree_path(repo, git, config, Some(cmd.from.clone()))?;
+    let to = resolve::resolve_worktree_path(repo, git, config, Some(cmd.to.clone()))?;
+
+    if from == to {
+        return Err(AppError::user(format!(
+            "source and destination are the same worktree: {}",
+            from.display()
+        ))
+        .into());
+    }
+
+    let mode = if cmd.copy {
+        TransferMode::Copy
+    } else {
+        TransferMode::Move
+    };
+    let summary = transfer_changes(git, repo.common_dir(), &from, &to, &cmd.paths, mode)?;
+
+    let mut stdout = io::stdout().lock();
+    if summary.is_empty() {
+        writeln!(stdout, "No uncommitted changes to transfer in {}", from.display())?;
+        return Ok(());
+    }
+
+    writeln!(
+        stdout,
+        "{} {} file(s) from {} to {}",
+        if mode == TransferMode::Copy { "Copied" } else { "Moved" },
+        summary.tracked.len() + summary.untracked.len(),
+        from.display(),
+        to.display()
+    )?;
+    for path in &summary.tracked {
+        writeln!(stdout, "  M {}", path)?;
+    }
+    for path in &summary.untracked {
+        writeln!(stdout, "  ? {}", path)?;
+    }
+    Ok(())
+}
+
+/// `from` の未コミット変更 (未追跡ファイルを含む) を `to` に適用する。
+/// どれか一つでもきれいに適用できない場合は、どちらの worktree も変更せずにエラーを返す。
+pub fn transfer_changes(
+    git: &GitRunner,
+    scratch_dir: &Path,
+    from: &Path,
+    to: &Path,
+    paths: &[String],
+    mode: TransferMode,
+) -> Result<TransferSummary> {
+    // リネームは削除と追加に分け、移動元と移動先の両方を対象にする
+    let tracked = list_z(
+        git,
+        from,
+        &["diff", "--no-renames", "--name-only", "-z", "HEAD"],
+        paths,
+    )?;
+    // 入れ子のリポジトリ (他の worktree など) は `dir/` として列挙されるため対象外にする
+    let untracked = list_z(
+        git,
+        from,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+        paths,
+    )?
+    .into_iter()
+    .filter(|entry| !entry.ends_with('/'))
+    .collect();
+    let summary = TransferSummary { tracked, untracked };
+    if summary.is_empty() {
+        return Ok(summary);
+    }
+
+    let patch_file = if summary.tracked.is_empty() {
+        None
+    } else {
+        let mut args = vec!["diff", "--no-renames", "--binary", "HEAD", "--"];
+        args.extend(paths.iter().map(String::as_str));
+        let patch = common::run_git_in(git, from, &args)?;
+
+        fs::create_dir_all(scratch_dir)
+            .with_context(|| format!("failed to create {}", scratch_dir.display()))?;
+        let file = scratch_dir.join(format!("transfer-{}.patch", std::process::id()));
+        fs::write(&file, patch)
+            .with_context(|| format!("failed to write {}", file.display()))?;
+        Some(file)
+    };
+
+    let result = apply_all(git, patch_file.as_deref(), from, to, &summary);
+    if let Some(file) = &patch_file {
+        let _ = fs::remove_file(file);
+    }
+    result?;
+
+    if mode == TransferMode::Move {
+        discard_changes(git, from, &summary)?;
+    }
+    Ok(summary)
+}
+
+fn apply_all(
+    git: &GitRunner,
+    patch_file: Option<&Path>,
+    from: &Path,
+    to: &Path,
+    summary: &TransferSummary,
+) -> Result<()> {
+    let mut conflicts = Vec::new();
+
+    if let Some(file) = patch_file {
+        let file = file.to_string_lossy().into_owned();
+        let check = git
+            .run_with_status_in(to, ["apply", "--check", "--binary", file.as_str()])
+            .map_err(|err| AppError::git(err.to_string()))?;
+        if !check.status.success() {
+            conflicts.extend(
+                check
+                    .stderr()
+                    .lines()
+                    .map(|line| line.trim().trim_start_matches("error: ").to_string())
+                    .filter(|line| !line.is_empty()),
+            );
+        }
+    }
+
+    for path in &summary.untracked {
+        let dest = to.join(path);
+        if dest.exists() && fs::read(&dest).ok() != fs::read(from.join(path)).ok() {
+            conflicts.push(format!("{}: already exists in destination", path));
+        }
+    }
+
+    if !conflicts.is_empty() {
+        return Err(AppError::user(format!(
+            "changes do not apply cleanly to {}; nothing was changed\n  {}",
+            to.display(),
+            conflicts.join("\n  ")
+        ))
+        .into());
+    }
+
+    if let Some(file) = patch_file {
+        let file = file.to_string_lossy().into_owned();
+        common::run_git_in(git, to, &["apply", "--binary", file.as_str()])?;
+    }
+
+    for path in &summary.untracked {
+        let src = from.join(path);
+        let dest = to.join(path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        fs::copy(&src, &dest)
+            .with_context(|| format!("failed to copy file to {}", dest.display()))?;
+    }
+
+    Ok(())
+}
+
+fn discard_changes(git: &GitRunner, from: &Path, summary: &TransferSummary) -> Result<()> {
+    if !summary.tracked.is_empty() {
+        let mut args = vec![
+            "--literal-pathspecs",
+            "restore",
+            "--source=HEAD",
+            "--staged",
+            "--worktree",
+            "--",
+        ];
+        args.extend(summary.tracked.iter().map(String::as_str));
+        common::run_git_in(git, from, &args)?;
+    }
+
+    for path in &summary.untracked {
+        let file: PathBuf = from.join(path);
+        fs::remove_file(&file).with_context(|| format!("failed to remove {}", file.display()))?;
+    }
+    Ok(())
+}
+
+fn list_z(git: &GitRunner, dir: &Path, base: &[&str], paths: &[String]) -> Result<Vec<String>> {
+    let mut args = base.to_vec();
+    args.push("--");
+    args.extend(paths.iter().map(String::as_str));
+    let output = common::run_git_in(git, dir, &args)?;
+    Ok(output
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect())
+}
@@ -0,0 +1,444 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::{RestoreCommand, TrashAction, TrashCommand};
+use crate::config::Config;
+use crate::config::types::Hook;
+use crate::error::AppError;
+use crate::git::rev::RepoContext;
+use crate::git::runner::GitRunner;
+use crate::git::worktree::WorktreeInfo;
+use crate::worktree::metadata::{self, WorktreeMetadata};
+use crate::worktree::{common, naming};
+
+/// ゴミ箱のコミットを gc から守る参照の接頭辞
+const TRASH_REF_PREFIX: &str = "refs/gwe/trash/";
+
+const ENTRY_FILE: &str = "entry.json";
+const PATCH_FILE: &str = "changes.patch";
+const FILES_ARCHIVE: &str = "files.tar";
+
+/// 削除した worktree の記録 (`<common-dir>/gwe/trash/<id>/entry.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    /// 削除時のブランチ (detached HEAD なら None)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// 削除時の HEAD コミット
+    pub head: String,
+    /// 削除前の worktree の絶対パス
+    pub path: PathBuf,
+    /// 削除した日時 (UNIX 時刻, 秒)
+    pub removed_at: u64,
+    #[serde(default)]
+    pub metadata: WorktreeMetadata,
+}
+
+pub fn run(repo: &RepoContext, git: &GitRunner, cmd: &TrashCommand) -> Result<()> {
+    match &cmd.action {
+        TrashAction::List => list(repo),
+        TrashAction::Purge { older_than } => purge(repo, git, older_than.as_deref()),
+    }
+}
+
+/// worktree を削除する前に、ブランチの先端・未コミット差分・未追跡ファイル・
+/// フックでコピーされたローカルファイルをゴミ箱へ退避する
+pub fn save(
+    repo: &RepoContext,
+    git: &GitRunner,
+    config: &Config,
+    info: &WorktreeInfo,
+) -> Result<TrashEntry> {
+    let path = common::normalize_path(&info.path);
+    let head = common::run_git_in(git, &path, &["rev-parse", "--verify", "HEAD"])?
+        .trim()
+        .to_string();
+
+    let (id, dir) = new_entry_dir(repo)?;
+    let entry = TrashEntry {
+        id: id.clone(),
+        branch: info.branch.clone(),
+        head: head.clone(),
+        path: path.clone(),
+        removed_at: common::unix_now(),
+        metadata: metadata::load(&path),
+    };
+
+    let result = (|| -> Result<()> {
+        let patch = common::run_git_in(git, &path, &["diff", "--binary", "HEAD"])?;
+        if !patch.is_empty() {
+            fs::write(dir.join(PATCH_FILE), patch)
+                .with_context(|| format!("failed to write {}", dir.join(PATCH_FILE).display()))?;
+        }
+
+        let files = local_files(git, config, &path)?;
+        if !files.is_empty() {
+            write_archive(&path, &files, &dir.join(FILES_ARCHIVE))?;
+        }
+
+        let json = serde_json::to_string_pretty(&entry).context("failed to serialize trash entry")?;
+        fs::write(dir.join(ENTRY_FILE), json)
+            .with_context(|| format!("failed to write {}", dir.join(ENTRY_FILE).display()))?;
+
+        let reference = format!("{}{}", TRASH_REF_PREFIX, id);
+        common::run_git_in(git, &path, &["update-ref", reference.as_str(), head.as_str()])?;
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(err.context("failed to save the worktree to the trash"));
+    }
+    Ok(entry)
+}
+
+/// ゴミ箱から worktree を元のパスに作り直す
+pub fn restore(repo: &RepoContext, git: &GitRunner, cmd: &RestoreCommand) -> Result<()> {
+    let entry = load_entry(repo, &cmd.id)?;
+    let dir = trash_dir(repo).join(&entry.id);
+    let reference = format!("{}{}", TRASH_REF_PREFIX, entry.id);
+
+    if entry.path.exists()
+        && fs::read_dir(&entry.path)
+            .map(|mut items| items.next().is_some())
+            .unwrap_or(true)
+    {
+        return Err(AppError::user(format!(
+            "cannot restore {}: {} already exists",
+            entry.id,
+            entry.path.display()
+        ))
+        .into());
+    }
+
+    let path_arg = entry.path.to_string_lossy().into_owned();
+    let add_args: Vec<String> = match &entry.branch {
+        Some(branch) if common::branch_exists(git, branch)? => {
+            let tip = git
+                .run(["rev-parse", "--verify", branch.as_str()])
+                .map(|output| output.stdout().trim().to_string())
+                .unwrap_or_default();
+            if tip != entry.head {
+                return Err(AppError::user(format!(
+                    "cannot restore {}: branch '{}' has moved since it was removed",
+                    entry.id, branch
+                ))
+                .into());
+            }
+            vec!["worktree".into(), "add".into(), path_arg, branch.clone()]
+        }
+        Some(branch) => vec![
+            "worktree".into(),
+            "add".into(),
+            "-b".into(),
+            branch.clone(),
+            path_arg,
+            reference.clone(),
+        ],
+        None => vec![
+            "worktree".into(),
+            "add".into(),
+            "--detach".into(),
+            path_arg,
+            reference.clone(),
+        ],
+    };
+    git.run(add_args)
+        .map_err(|err| AppError::git(format!("failed to recreate the worktree: {}", err)))?;
+
+    let patch = dir.join(PATCH_FILE);
+    if patch.exists() {
+        let patch_arg = patch.to_string_lossy().into_owned();
+        common::run_git_in(git, &entry.path, &["apply", "--binary", patch_arg.as_str()])
+            .context("failed to reapply uncommitted changes")?;
+    }
+
+    let archive = dir.join(FILES_ARCHIVE);
+    if archive.exists() {
+        let file = File::open(&archive)
+            .with_context(|| format!("failed to open {}", archive.display()))?;
+        tar::Archive::new(file)
+            .unpack(&entry.path)
+            .with_context(|| format!("failed to extract {}", archive.display()))?;
+    }
+
+    if entry.metadata != WorktreeMetadata::default() {
+        metadata::save(&entry.path, &entry.metadata)?;
+    }
+
+    remove_entry(git, &dir, &entry.id)?;
+
+    let mut stdout = io::stdout().lock();
+    writeln!(
+        stdout,
+        "✓ Restored {} at {}",
+        entry.branch.as_deref().unwrap_or("detached HEAD"),
+        entry.path.display()
+    )?;
+    Ok(())
+}
+
+fn list(repo: &RepoContext) -> Result<()> {
+    let entries = load_entries(repo)?;
+    let mut stdout = io::stdout().lock();
+    if entries.is_empty() {
+        writeln!(stdout, "Trash is empty")?;
+        return Ok(());
+    }
+
+    let rows: Vec<[String; 4]> = entries
+        .iter()
+        .map(|entry| {
+            [
+                entry.id.clone(),
+                entry.branch.clone().unwrap_or_else(|| "(detached)".to_string()),
+                format_time(entry.removed_at),
+                entry.path.display().to_string(),
+            ]
+        })
+        .collect();
+    let headers = ["ID", "BRANCH", "REMOVED", "PATH"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print = |out: &mut dyn Write, cells: [&str; 4]| -> io::Result<()> {
+        writeln!(
+            out,
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            cells[0],
+            cells[1],
+            cells[2],
+            cells[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        )
+    };
+    print(&mut stdout, headers)?;
+    for row in &rows {
+        print(&mut stdout, [&row[0], &row[1], &row[2], &row[3]])?;
+    }
+    Ok(())
+}
+
+fn purge(repo: &RepoContext, git: &GitRunner, older_than: Option<&str>) -> Result<()> {
+    let min_age = older_than.map(parse_age).transpose()?;
+    let now = common::unix_now();
+
+    let mut purged = 0;
+    for entry in load_entries(repo)? {
+        if let Some(age) = min_age
+            && now.saturating_sub(entry.removed_at) < age
+        {
+            continue;
+        }
+        remove_entry(git, &trash_dir(repo).join(&entry.id), &entry.id)?;
+        purged += 1;
+    }
+
+    writeln!(io::stdout().lock(), "Purged {} trash entries", purged)?;
+    Ok(())
+}
+
+/// `14d` のような期間を秒数に変換する (s/m/h/d/w)
+pub fn parse_age(value: &str) -> Result<u64> {
+    let value = value.trim();
+    let invalid = || AppError::user(format!("invalid duration '{}' (expected e.g. 30m, 12h, 14d, 2w)", value));
+    let split = value.find(|ch: char| !ch.is_ascii_digit()).ok_or_else(invalid)?;
+    let (number, unit) = value.split_at(split);
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let unit = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3_600,
+        "d" => 86_400,
+        "w" => 604_800,
+        _ => return Err(invalid().into()),
+    };
+    Ok(number.checked_mul(unit).ok_or_else(invalid)?)
+}
+
+/// `from` の配下から削除した記録の復元先を `to` の配下に付け替える
+pub fn rebase_paths(repo: &RepoContext, from: &Path, to: &Path) -> Result<()> {
+    for mut entry in load_entries(repo)? {
+        let Ok(relative) = entry.path.strip_prefix(from) else {
+            continue;
+        };
+        entry.path = to.join(relative);
+        let file = trash_dir(repo).join(&entry.id).join(ENTRY_FILE);
+        let json = serde_json::to_string_pretty(&entry).context("failed to serialize trash entry")?;
+        fs::write(&file, json).with_context(|| format!("failed to write {}", file.display()))?;
+    }
+    Ok(())
+}
+
+fn trash_dir(repo: &RepoContext) -> PathBuf {
+    repo.common_dir().join("gwe").join("trash")
+}
+
+/// 重ならない ID (`20261019-3f2a`) でエントリ用ディレクトリを作る
+fn new_entry_dir(repo: &RepoContext) -> Result<(String, PathBuf)> {
+    let root = trash_dir(repo);
+    fs::create_dir_all(&root).with_context(|| format!("failed to create {}", root.display()))?;
+    let date = naming::today();
+    for _ in 0..16 {
+        let id = format!("{}-{:04x}", date, naming::random_hex());
+        let dir = root.join(&id);
+        match fs::create_dir(&dir) {
+            Ok(()) => return Ok((id, dir)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to create {}", dir.display()));
+            }
+        }
+    }
+    Err(AppError::internal("failed to allocate a trash id").into())
+}
+
+fn load_entry(repo: &RepoContext, id: &str) -> Result<TrashEntry> {
+    let file = trash_dir(repo).join(id).join(ENTRY_FILE);
+    if id.contains(['/', '\\']) || !file.is_file() {
+        return Err(AppError::user(format!(
+            "trash entry '{}' not found\nRun 'gwe trash list' to see removed worktrees.",
+            id
+        ))
+        .into());
+    }
+    let content =
+        fs::read_to_string(&file).with_context(|| format!("failed to read {}", file.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("failed to parse {}", file.display()))
+}
+
+/// 新しいものから順に返す
+fn load_entries(repo: &RepoContext) -> Result<Vec<TrashEntry>> {
+    let root = trash_dir(repo);
+    let Ok(items) = fs::read_dir(&root) else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    for item in items {
+        let item = item?;
+        let id = item.file_name().to_string_lossy().into_owned();
+        if let Ok(entry) = load_entry(repo, &id) {
+            entries.push(entry);
+        }
+    }
+    entries.sort_by(|a, b| b.removed_at.cmp(&a.removed_at).then(b.id.cmp(&a.id)));
+    Ok(entries)
+}
+
+fn remove_entry(git: &GitRunner, dir: &Path, id: &str) -> Result<()> {
+    let reference = format!("{}{}", TRASH_REF_PREFIX, id);
+    let _ = git.run(["update-ref", "-d", reference.as_str()]);
+    fs::remove_dir_all(dir).with_context(|| format!("failed to remove {}", dir.display()))
+}
+
+/// 退避するファイル: 未追跡ファイルと、コピー系フックの対象になっている ignore ファイル
+fn local_files(git: &GitRunner, config: &Config, worktree: &Path) -> Result<Vec<String>> {
+    let untracked = common::run_git_in(
+        git,
+        worktree,
+        &["ls-files", "--others", "--exclude-standard", "-z"],
+    )?;
+    let mut files: Vec<String> = untracked
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let mut patterns = Vec::new();
+    let mut copied = Vec::new();
+    for hook in &config.hooks.post_create {
+        match hook {
+            Hook::GlobCopy(hook) => patterns.extend(glob::Pattern::new(&hook.pattern).ok()),
+            Hook::Copy(hook) => copied.push(hook.to.clone()),
+            Hook::Command(_) => {}
+        }
+    }
+    if patterns.is_empty() && copied.is_empty() {
+        return Ok(files);
+    }
+
+    let ignored = common::run_git_in(
+        git,
+        worktree,
+        &["ls-files", "--others", "--ignored", "--exclude-standard", "-z"],
+    )?;
+    files.extend(
+        ignored
+            .split('\0')
+            .filter(|entry| !entry.is_empty())
+            .filter(|entry| {
+                // ディレクトリごとコピーされたファイルも対象にするため祖先も照合する
+                Path::new(entry).ancestors().any(|ancestor| {
+                    patterns.iter().any(|pattern| pattern.matches_path(ancestor))
+                        || copied.iter().any(|to| ancestor == to.as_path())
+                })
+            })
+            .map(str::to_string),
+    );
+    Ok(files)
+}
+
+fn write_archive(root: &Path, files: &[String], archive: &Path) -> Result<()> {
+    let file =
+        File::create(archive).with_context(|| format!("failed to create {}", archive.display()))?;
+    let mut builder = tar::Builder::new(file);
+    builder.follow_symlinks(false);
+    for name in files {
+        builder
+            .append_path_with_name(root.join(name), name)
+            .with_context(|| format!("failed to archive {}", name))?;
+    }
+    builder
+        .into_inner()
+        .and_then(|mut file| file.flush())
+        .with_context(|| format!("failed to write {}", archive.display()))
+}
+
+/// UNIX 時刻を `YYYY-MM-DD HH:MM` (UTC) に整形する
+fn format_time(secs: u64) -> String {
+    let (year, month, day) = naming::civil_from_days((secs / 86_400) as i64);
+    let rest = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        rest / 3_600,
+        rest % 3_600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_age_accepts_common_units() {
+        assert_eq!(parse_age("14d").unwrap(), 14 * 86_400);
+        assert_eq!(parse_age("12h").unwrap(), 12 * 3_600);
+        assert_eq!(parse_age("2w").unwrap(), 14 * 86_400);
+        assert_eq!(parse_age("90s").unwrap(), 90);
+        assert!(parse_age("14").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+        assert!(parse_age("99999999999999999w").is_err());
+    }
+
+    #[test]
+    fn format_time_uses_utc() {
+        assert_eq!(format_time(0), "1970-01-01 00:00");
+        assert_eq!(format_time(20_744 * 86_400 + 3_600 * 13 + 60 * 5), "2026-10-18 13:05");
+    }
+}
@@ -103,3 +103,405 @@ fn post_create_hooks_copy_and_command_are_run() {
     );
 }
 
+
+#[test]
+fn add_detach_names_worktree_after_tag() {
+    let repo = TestRepo::new();
+    repo.git(&["tag", "v1.0.0"]);
+    let worktree_path = repo.worktree_path_for("v1.0.0");
+
+    repo.command()
+        .args(["add", "--detach", "v1.0.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(worktree_path.to_string_lossy()));
+
+    assert!(worktree_path.exists(), "detached worktree should be named after the tag");
+
+    let table = String::from_utf8(
+        repo.command()
+            .arg("list")
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone(),
+    )
+    .unwrap();
+    assert!(
+        table.contains("(detached v1.0.0)"),
+        "list should show the detached tag:\n{}",
+        table
+    );
+}
+
+#[test]
+fn add_detach_uses_short_sha_for_commits() {
+    let repo = TestRepo::new();
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .unwrap();
+    let short_sha = String::from_utf8(output.stdout).unwrap().trim().to_string();
+
+    repo.command()
+        .args(["add", "--detach", "main"])
+        .assert()
+        .success();
+
+    assert!(repo.worktree_path_for(&short_sha).exists());
+}
+
+#[test]
+fn add_orphan_creates_empty_unborn_branch() {
+    let repo = TestRepo::new();
+    let worktree_path = repo.worktree_path_for("gh-pages");
+
+    repo.command()
+        .args(["add", "--orphan", "gh-pages"])
+        .assert()
+        .success();
+
+    assert!(worktree_path.exists());
+    assert!(
+        !worktree_path.join("README.md").exists(),
+        "orphan worktree should not contain files from other branches"
+    );
+
+    let head = std::process::Command::new("git")
+        .current_dir(&worktree_path)
+        .args(["symbolic-ref", "HEAD"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(head.stdout).unwrap().trim(),
+        "refs/heads/gh-pages"
+    );
+}
+
+fn commit_directories(repo: &TestRepo, dirs: &[&str]) {
+    for dir in dirs {
+        fs::create_dir_all(repo.path().join(dir)).unwrap();
+        fs::write(repo.path().join(dir).join("file.txt"), dir).unwrap();
+    }
+    repo.git(&["add", "."]);
+    repo.git(&["commit", "-q", "-m", "add directories"]);
+}
+
+#[test]
+fn add_sparse_checks_out_only_requested_directories() {
+    let repo = TestRepo::new();
+    commit_directories(&repo, &["app", "docs", "vendor"]);
+    let worktree_path = repo.worktree_path_for("feature/sparse");
+
+    repo.command()
+        .args(["add", "-b", "feature/sparse", "--sparse", "app", "docs"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Sparse checkout (cone): app, docs"));
+
+    assert!(worktree_path.join("README.md").exists());
+    assert!(worktree_path.join("app").join("file.txt").exists());
+    assert!(worktree_path.join("docs").join("file.txt").exists());
+    assert!(!worktree_path.join("vendor").exists());
+
+    #[derive(serde::Deserialize)]
+    struct Entry {
+        branch: Option<String>,
+        sparse: bool,
+        sparse_patterns: Vec<String>,
+    }
+    let output = repo
+        .command()
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let entries: Vec<Entry> = serde_json::from_slice(&output).unwrap();
+    let main = entries.iter().find(|e| e.branch.as_deref() == Some("main")).unwrap();
+    assert!(!main.sparse);
+    let sparse = entries
+        .iter()
+        .find(|e| e.branch.as_deref() == Some("feature/sparse"))
+        .unwrap();
+    assert!(sparse.sparse);
+    assert_eq!(sparse.sparse_patterns, vec!["app", "docs"]);
+}
+
+#[test]
+fn add_uses_profile_sparse_over_default() {
+    let repo = TestRepo::new();
+    commit_directories(&repo, &["app", "docs"]);
+    repo.set_config("gwe.sparse.default", "app");
+    repo.set_config("gwe.profile.docs.sparse", "docs");
+
+    repo.command()
+        .args(["add", "-b", "feature/default-sparse"])
+        .assert()
+        .success();
+    let default_path = repo.worktree_path_for("feature/default-sparse");
+    assert!(default_path.join("app").exists());
+    assert!(!default_path.join("docs").exists());
+
+    repo.command()
+        .args(["add", "-b", "feature/profile-sparse", "--profile", "docs"])
+        .assert()
+        .success();
+    let profile_path = repo.worktree_path_for("feature/profile-sparse");
+    assert!(profile_path.join("docs").exists());
+    assert!(!profile_path.join("app").exists());
+
+    repo.command()
+        .args(["add", "-b", "feature/full", "--no-sparse"])
+        .assert()
+        .success();
+    let full_path = repo.worktree_path_for("feature/full");
+    assert!(full_path.join("app").exists() && full_path.join("docs").exists());
+}
+
+#[test]
+fn add_initializes_submodules_from_local_objects() {
+    let repo = TestRepo::new();
+    let upstream = tempfile::TempDir::new().unwrap();
+    let upstream_path = upstream.path().join("lib");
+    fs::create_dir_all(&upstream_path).unwrap();
+    common::run_git(&upstream_path, &["init", "-q", "-b", "main"]);
+    common::run_git(&upstream_path, &["config", "user.name", "gwe-test"]);
+    common::run_git(&upstream_path, &["config", "user.email", "gwe@example.com"]);
+    fs::write(upstream_path.join("lib.txt"), "lib").unwrap();
+    common::run_git(&upstream_path, &["add", "lib.txt"]);
+    common::run_git(&upstream_path, &["commit", "-q", "-m", "lib"]);
+
+    let upstream_url = upstream_path.to_string_lossy().to_string();
+    repo.git(&[
+        "-c",
+        "protocol.file.allow=always",
+        "submodule",
+        "add",
+        "-q",
+        &upstream_url,
+        "libs/lib",
+    ]);
+    repo.git(&["commit", "-q", "-m", "add submodule"]);
+    repo.set_config("gwe.submodules", "init");
+
+    // The original remote is gone: initialization must reuse the main worktree's objects
+    let moved = upstream.path().join("moved");
+    fs::rename(&upstream_path, &moved).unwrap();
+
+    repo.command()
+        .args(["add", "-b", "feature/submodules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Initializing submodules"));
+
+    let worktree_path = repo.worktree_path_for("feature/submodules");
+    assert!(worktree_path.join("libs").join("lib").join("lib.txt").exists());
+
+    let remote = std::process::Command::new("git")
+        .current_dir(worktree_path.join("libs").join("lib"))
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8(remote.stdout).unwrap().trim(), upstream_url);
+}
+
+#[test]
+fn add_submodules_none_leaves_submodules_empty() {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".gitmodules"),
+        "[submodule \"libs/lib\"]\n\tpath = libs/lib\n\turl = ../lib\n",
+    )
+    .unwrap();
+    repo.git(&["add", ".gitmodules"]);
+    repo.git(&["commit", "-q", "-m", "gitmodules"]);
+    repo.set_config("gwe.submodules", "recursive");
+
+    repo.command()
+        .args(["add", "-b", "feature/no-submodules", "--submodules", "none"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Initializing submodules").not());
+}
+
+#[test]
+fn add_rejects_branches_differing_only_in_case() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "feature/login"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["add", "-b", "Feature/Login"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'Feature/Login' and 'feature/login'"));
+}
+
+#[test]
+fn add_suffixes_colliding_paths_when_configured() {
+    let repo = TestRepo::new();
+    repo.set_config("gwe.worktrees.onCollision", "suffix");
+    repo.command()
+        .args(["add", "-b", "feature/login"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["add", "-b", "Feature/Login"])
+        .assert()
+        .success();
+
+    assert!(repo.worktree_path_for("Feature/Login-2").exists());
+}
+
+#[test]
+fn add_detects_branches_sanitized_to_the_same_path() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "fix/a_b"]).assert().success();
+    repo.command().args(["add", "-b", "fix/_con"]).assert().success();
+
+    repo.command()
+        .args(["add", "-b", "fix/a|b"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'fix/a|b' and 'fix/a_b'"));
+    repo.command()
+        .args(["add", "-b", "fix/con"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'fix/con' and 'fix/_con'"));
+
+    repo.set_config("gwe.worktrees.onCollision", "suffix");
+    repo.command().args(["add", "-b", "fix/a|b"]).assert().success();
+    repo.command().args(["add", "-b", "fix/con"]).assert().success();
+
+    assert!(repo.worktree_path_for("fix/a_b-2").exists());
+    assert!(repo.worktree_path_for("fix/_con-2").exists());
+}
+
+#[test]
+fn add_escapes_windows_reserved_names() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "fix/con"])
+        .assert()
+        .success();
+
+    assert!(repo.worktree_path_for("fix/_con").exists());
+}
+
+#[test]
+fn add_name_from_slugifies_text() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "--name-from", "Fix login timeout on SSO"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Created worktree 'fix/login-timeout-on-sso'"));
+
+    assert!(repo.worktree_path_for("fix/login-timeout-on-sso").exists());
+}
+
+#[test]
+fn add_without_branch_value_generates_unique_name() {
+    let repo = TestRepo::new();
+    let first = repo.command().args(["add", "-b"]).assert().success();
+    let second = repo.command().args(["add", "-b"]).assert().success();
+
+    let first = String::from_utf8(first.get_output().stdout.clone()).unwrap();
+    let second = String::from_utf8(second.get_output().stdout.clone()).unwrap();
+    assert!(first.contains("Created worktree 'wip/"), "{first}");
+    assert_ne!(first.lines().next(), second.lines().next());
+}
+
+#[test]
+fn add_enforces_branch_prefix_and_pattern() {
+    let repo = TestRepo::new();
+    repo.set_config("gwe.branch.prefix", "feature/, fix/");
+    repo.set_config("gwe.branch.pattern", "[a-z]+/[a-z0-9-]+");
+
+    repo.command()
+        .args(["add", "-b", "misc/thing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must start with one of: feature/, fix/"));
+
+    repo.command()
+        .args(["add", "-b", "feature/Upper_Case"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("does not match gwe.branch.pattern"));
+
+    repo.command()
+        .args(["add", "-b", "feature/ok-1"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn add_rejects_invalid_ref_names() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "bad..name"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'bad..name' is not a valid branch name"));
+}
+
+#[test]
+fn add_multiplier_creates_more_than_five_without_panes() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-x", "7", "-b", "exp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[7/7] Created worktree 'exp-7'"));
+
+    for i in 1..=7 {
+        assert!(repo.worktree_path_for(&format!("exp-{i}")).exists());
+    }
+}
+
+#[test]
+fn add_accepts_explicit_branch_list() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "try/a", "-b", "try/b", "-b", "try/c"])
+        .assert()
+        .success();
+
+    for name in ["try/a", "try/b", "try/c"] {
+        assert!(repo.worktree_path_for(name).exists());
+    }
+}
+
+#[test]
+fn add_multiplier_uses_configured_suffix() {
+    let repo = TestRepo::new();
+    repo.set_config("gwe.multi.suffix", "/run-{0n}");
+    repo.command()
+        .args(["add", "-x", "10", "-b", "exp"])
+        .assert()
+        .success();
+
+    assert!(repo.worktree_path_for("exp/run-01").exists());
+    assert!(repo.worktree_path_for("exp/run-10").exists());
+}
+
+#[test]
+fn terminal_tools_keep_pane_limit() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["claude", "-x", "6", "-b", "exp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("at most 5 panes"));
+
+    assert!(!repo.worktree_path_for("exp-1").exists());
+}
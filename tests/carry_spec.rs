@@ -0,0 +1,82 @@
+mod common;
+
+use common::{TestRepo, git_stdout};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn carry_moves_uncommitted_changes_to_new_worktree() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "staged change").unwrap();
+    repo.git(&["add", "README.md"]);
+    fs::write(repo.path().join("notes.txt"), "untracked").unwrap();
+
+    repo.command()
+        .args(["add", "-b", "feature/carry", "--carry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Changes moved to"));
+
+    let worktree_path = repo.worktree_path_for("feature/carry");
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("README.md")).unwrap(),
+        "staged change"
+    );
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("notes.txt")).unwrap(),
+        "untracked"
+    );
+    assert!(
+        git_stdout(&worktree_path, &["diff", "--cached", "--name-only"]).contains("README.md"),
+        "staged changes should stay staged"
+    );
+
+    assert_eq!(
+        fs::read_to_string(repo.path().join("README.md")).unwrap(),
+        "hello world"
+    );
+    assert!(!repo.path().join("notes.txt").exists());
+    assert!(git_stdout(repo.path(), &["stash", "list"]).is_empty());
+}
+
+#[test]
+fn from_stash_applies_stash_and_keeps_it() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "stashed change").unwrap();
+    repo.git(&["stash", "push", "-q"]);
+
+    repo.command()
+        .args(["add", "-b", "feature/stash", "--from-stash", "stash@{0}"])
+        .assert()
+        .success();
+
+    let worktree_path = repo.worktree_path_for("feature/stash");
+    assert_eq!(
+        fs::read_to_string(worktree_path.join("README.md")).unwrap(),
+        "stashed change"
+    );
+    assert_eq!(git_stdout(repo.path(), &["stash", "list"]).lines().count(), 1);
+}
+
+#[test]
+fn from_stash_reports_conflicts_and_keeps_stash() {
+    let repo = TestRepo::new();
+    repo.git(&["branch", "other"]);
+    fs::write(repo.path().join("README.md"), "stashed change").unwrap();
+    repo.git(&["stash", "push", "-q"]);
+
+    let other = repo.path().join("other-checkout");
+    repo.git(&["worktree", "add", "-q", &other.to_string_lossy(), "other"]);
+    fs::write(other.join("README.md"), "diverged").unwrap();
+    common::run_git(&other, &["commit", "-q", "-am", "diverge"]);
+    repo.git(&["worktree", "remove", &other.to_string_lossy()]);
+
+    repo.command()
+        .args(["add", "-b", "feature/conflict", "other", "--from-stash", "stash@{0}"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Conflicting files"))
+        .stderr(predicate::str::contains("README.md"));
+
+    assert_eq!(git_stdout(repo.path(), &["stash", "list"]).lines().count(), 1);
+}
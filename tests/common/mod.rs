@@ -92,6 +92,13 @@ impl TestRepo {
     }
 }
 
+/// Create a worktree on a new branch with `gwe add -b` and return its path.
+#[allow(dead_code)]
+pub fn add_worktree(repo: &TestRepo, branch: &str) -> PathBuf {
+    repo.command().args(["add", "-b", branch]).assert().success();
+    repo.worktree_path_for(branch)
+}
+
 #[allow(dead_code)]
 pub fn normalize_path(path: &Path) -> PathBuf {
     path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
@@ -111,3 +118,25 @@ pub fn run_git(dir: &Path, args: &[&str]) {
         status.code()
     );
 }
+
+/// Whether `refs/heads/<branch>` exists in the repository at `dir`.
+#[allow(dead_code)]
+pub fn branch_exists(dir: &Path, branch: &str) -> bool {
+    StdCommand::new("git")
+        .current_dir(dir)
+        .args(["show-ref", "--verify", "--quiet", &format!("refs/heads/{branch}")])
+        .status()
+        .expect("git show-ref")
+        .success()
+}
+
+/// Run git in `dir` and return its trimmed stdout.
+#[allow(dead_code)]
+pub fn git_stdout(dir: &Path, args: &[&str]) -> String {
+    let output = StdCommand::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("git");
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
@@ -3,6 +3,7 @@ use common::TestRepo;
 use predicates::prelude::*;
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn config_set_get_unset() {
     let repo = TestRepo::new();
 
@@ -34,6 +35,7 @@ fn config_set_get_unset() {
 }
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn config_add_multiple_values() {
     let repo = TestRepo::new();
 
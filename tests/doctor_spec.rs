@@ -0,0 +1,224 @@
+mod common;
+
+use assert_cmd::Command;
+use common::TestRepo;
+use predicates::prelude::*;
+use std::fs;
+
+fn doctor(repo: &TestRepo) -> Command {
+    let mut cmd = repo.command();
+    cmd.arg("doctor").env("GWE_SHELL_INTEGRATION", "1");
+    cmd
+}
+
+fn worktree_list(repo: &TestRepo) -> String {
+    let output = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["worktree", "list", "--porcelain"])
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn doctor_reports_nothing_for_a_healthy_repository() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/ok"]).assert().success();
+
+    doctor(&repo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn doctor_warns_about_missing_shell_integration() {
+    let repo = TestRepo::new();
+
+    repo.command()
+        .arg("doctor")
+        .env_remove("GWE_SHELL_INTEGRATION")
+        .env("SHELL", "/bin/zsh")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("warning  shell integration is not loaded"))
+        .stdout(predicate::str::contains("gwe init --shell zsh"));
+
+    repo.command()
+        .args(["shell-init", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("export GWE_SHELL_INTEGRATION=1"));
+}
+
+#[test]
+fn doctor_fix_prunes_worktrees_whose_directory_is_gone() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/gone");
+    repo.command().args(["add", "-b", "feature/gone"]).assert().success();
+    fs::remove_dir_all(&path).unwrap();
+
+    doctor(&repo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is prunable"))
+        .stdout(predicate::str::contains("fix: git worktree prune"));
+
+    doctor(&repo)
+        .arg("--fix")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned stale worktree entries"));
+    assert!(!worktree_list(&repo).contains("feature/gone"));
+    doctor(&repo).assert().success().stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn doctor_fix_repairs_manually_moved_worktrees() {
+    let repo = TestRepo::new();
+    let old = repo.worktree_path_for("feature/old");
+    let new = repo.worktree_path_for("feature/new");
+    repo.command().args(["add", "-b", "feature/old"]).assert().success();
+    fs::rename(&old, &new).unwrap();
+
+    doctor(&repo)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("was moved without git"))
+        .stdout(predicate::str::contains("is prunable").not())
+        .stderr(predicate::str::contains("run 'gwe doctor --fix'"));
+
+    doctor(&repo)
+        .arg("--fix")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Repaired 1 worktree link(s)"));
+    let list = worktree_list(&repo);
+    assert!(list.contains(&*common::normalize_path(&new).to_string_lossy()));
+    assert!(list.contains("branch refs/heads/feature/old"));
+    doctor(&repo).assert().success().stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn doctor_fix_repairs_broken_gitdir_files() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/link");
+    repo.command().args(["add", "-b", "feature/link"]).assert().success();
+    fs::write(path.join(".git"), "gitdir: /nonexistent/worktrees/link\n").unwrap();
+
+    doctor(&repo)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("broken gitdir link"));
+    doctor(&repo).arg("--fix").assert().success();
+    doctor(&repo).assert().success().stdout(predicate::str::contains("No problems found"));
+}
+
+#[test]
+fn doctor_fix_imports_or_deletes_orphan_directories() {
+    let repo = TestRepo::new();
+    let stray = repo.worktree_path_for("stray");
+    fs::create_dir_all(&stray).unwrap();
+    fs::write(stray.join("notes.txt"), "keep me").unwrap();
+
+    doctor(&repo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is not a registered worktree"));
+
+    // 端末でなければ何もしない
+    doctor(&repo)
+        .arg("--fix")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Kept"));
+    assert!(stray.exists());
+
+    doctor(&repo)
+        .args(["--fix", "--orphans", "import"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("as 'stray'"));
+    assert!(worktree_list(&repo).contains("branch refs/heads/stray"));
+    assert_eq!(fs::read_to_string(stray.join("notes.txt")).unwrap(), "keep me");
+    assert_eq!(fs::read_to_string(stray.join("README.md")).unwrap(), "hello world");
+
+    let other = repo.worktree_path_for("leftover/dir");
+    fs::create_dir_all(&other).unwrap();
+    fs::write(other.join("build.log"), "").unwrap();
+    doctor(&repo)
+        .args(["--fix", "--orphans", "delete"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Deleted"));
+    assert!(!other.exists());
+    assert!(stray.exists());
+}
+
+#[test]
+fn doctor_import_follows_branch_naming_policy() {
+    let repo = TestRepo::new();
+    let stray = repo.worktree_path_for("stray");
+    fs::create_dir_all(&stray).unwrap();
+    fs::write(stray.join("notes.txt"), "keep me").unwrap();
+    repo.set_config("gwe.branch.prefix", "feature/");
+
+    doctor(&repo)
+        .args(["--fix", "--orphans", "import"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must start with one of: feature/"));
+    assert!(!worktree_list(&repo).contains("branch refs/heads/stray"));
+    assert_eq!(fs::read_to_string(stray.join("notes.txt")).unwrap(), "keep me");
+}
+
+#[test]
+fn doctor_never_imports_or_deletes_standalone_clones() {
+    let repo = TestRepo::new();
+    let clone = repo.worktree_path_for("clone");
+    common::run_git(
+        repo.path(),
+        &["clone", "-q", repo.path().to_str().unwrap(), clone.to_str().unwrap()],
+    );
+    fs::write(clone.join("local.txt"), "not pushed").unwrap();
+
+    doctor(&repo)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("is a separate git repository"));
+
+    for action in ["import", "delete"] {
+        doctor(&repo)
+            .args(["--fix", "--orphans", action])
+            .assert()
+            .success();
+        assert!(clone.join(".git").is_dir());
+        assert_eq!(fs::read_to_string(clone.join("local.txt")).unwrap(), "not pushed");
+    }
+    assert!(!worktree_list(&repo).contains("branch refs/heads/clone"));
+}
+
+#[cfg(unix)]
+#[test]
+fn doctor_fix_removes_stale_index_locks() {
+    let repo = TestRepo::new();
+    let lock = repo.path().join(".git").join("index.lock");
+    fs::write(&lock, "").unwrap();
+
+    // 作られたばかりの lock は実行中の git のものかもしれないので触れない
+    doctor(&repo).assert().success().stdout(predicate::str::contains("No problems found"));
+
+    let status = std::process::Command::new("touch")
+        .args(["-d", "1 hour ago"])
+        .arg(&lock)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    doctor(&repo)
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("stale index.lock"));
+    doctor(&repo).arg("--fix").assert().success();
+    assert!(!lock.exists());
+}
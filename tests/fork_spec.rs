@@ -0,0 +1,77 @@
+mod common;
+
+use common::{TestRepo, add_worktree};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn fork_replicates_commits_changes_and_ignored_files() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".gitignore"), "node_modules/\n.env\n").unwrap();
+    repo.git(&["add", ".gitignore"]);
+    repo.git(&["commit", "-q", "-m", "ignore"]);
+
+    let source = add_worktree(&repo, "agent/attempt");
+    fs::write(source.join("work.txt"), "committed").unwrap();
+    common::run_git(&source, &["add", "work.txt"]);
+    common::run_git(&source, &["commit", "-q", "-m", "wip"]);
+    fs::write(source.join("README.md"), "half done").unwrap();
+    fs::write(source.join("notes.txt"), "untracked").unwrap();
+    fs::create_dir_all(source.join("node_modules").join("pkg")).unwrap();
+    fs::write(source.join("node_modules").join("pkg").join("index.js"), "1").unwrap();
+    fs::write(source.join(".env"), "TOKEN=abc").unwrap();
+
+    repo.command()
+        .args(["fork", "agent/attempt", "-b", "agent/retry"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Forked"))
+        .stdout(predicate::str::contains("node_modules/"));
+
+    let fork = repo.worktree_path_for("agent/retry");
+    assert_eq!(fs::read_to_string(fork.join("work.txt")).unwrap(), "committed");
+    assert_eq!(fs::read_to_string(fork.join("README.md")).unwrap(), "half done");
+    assert_eq!(fs::read_to_string(fork.join("notes.txt")).unwrap(), "untracked");
+    assert_eq!(fs::read_to_string(fork.join(".env")).unwrap(), "TOKEN=abc");
+    assert!(fork.join("node_modules").join("pkg").join("index.js").exists());
+
+    // 複製元はそのまま残る
+    assert_eq!(fs::read_to_string(source.join("README.md")).unwrap(), "half done");
+    assert!(source.join("notes.txt").exists());
+}
+
+#[test]
+fn fork_from_main_skips_nested_worktrees() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".gitignore"), "worktree/\n").unwrap();
+    repo.git(&["add", ".gitignore"]);
+    repo.git(&["commit", "-q", "-m", "ignore worktrees"]);
+    add_worktree(&repo, "feature/existing");
+    fs::write(repo.path().join("README.md"), "main change").unwrap();
+
+    repo.command()
+        .args(["fork", "@", "-b", "feature/from-main", "--no-ignored"])
+        .assert()
+        .success();
+
+    let fork = repo.worktree_path_for("feature/from-main");
+    assert_eq!(fs::read_to_string(fork.join("README.md")).unwrap(), "main change");
+    assert!(!fork.join("worktree").exists());
+
+    repo.command()
+        .args(["fork", "@", "-b", "feature/from-main-2"])
+        .assert()
+        .success();
+    let fork = repo.worktree_path_for("feature/from-main-2");
+    assert!(!fork.join("worktree").exists());
+}
+
+#[test]
+fn fork_rejects_unknown_worktree() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["fork", "ghost", "-b", "feature/x"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("worktree 'ghost' not found"));
+}
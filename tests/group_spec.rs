@@ -0,0 +1,123 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+use serde_json::Value;
+
+#[test]
+fn siblings_are_recorded_as_a_group() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-x", "3", "-b", "exp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Group: exp"));
+    repo.command().args(["add", "-b", "feature/other"]).assert().success();
+
+    let output = repo
+        .command()
+        .args(["list", "--group", "exp", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let entries: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let branches: Vec<&str> = entries
+        .iter()
+        .map(|entry| entry["branch"].as_str().unwrap())
+        .collect();
+    assert_eq!(branches, ["exp-1", "exp-2", "exp-3"]);
+    assert!(entries.iter().all(|entry| entry["group"] == "exp"));
+}
+
+#[test]
+fn explicit_group_name_and_duplicate_rejection() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "try/a", "-b", "try/b", "--group", "batch"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Group: batch"));
+
+    repo.command()
+        .args(["add", "-x", "2", "-b", "again", "--group", "batch"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("group 'batch' already exists"));
+}
+
+#[test]
+fn rm_group_removes_all_members_and_branches() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-x", "2", "-b", "exp"])
+        .assert()
+        .success();
+    repo.command().args(["add", "-b", "feature/keep"]).assert().success();
+
+    repo.command()
+        .args(["rm", "--group", "exp", "--with-branch"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed group 'exp' (2 worktrees)"));
+
+    assert!(!repo.worktree_path_for("exp-1").exists());
+    assert!(!repo.worktree_path_for("exp-2").exists());
+    assert!(repo.worktree_path_for("feature/keep").exists());
+
+    let branches = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["branch", "--list", "exp-*"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8(branches.stdout).unwrap().trim().is_empty());
+}
+
+#[test]
+fn unknown_group_is_reported() {
+    let repo = TestRepo::new();
+    for args in [
+        vec!["list", "--group", "ghost"],
+        vec!["rm", "--group", "ghost"],
+        vec!["open", "--group", "ghost"],
+    ] {
+        repo.command()
+            .args(&args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("group 'ghost' not found"));
+    }
+}
+
+#[test]
+fn open_requires_a_tool_for_headless_groups() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-x", "2", "-b", "exp"])
+        .assert()
+        .success();
+
+    repo.command()
+        .args(["open", "--group", "exp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --tool"));
+}
+
+#[test]
+fn rm_group_refuses_when_a_member_has_work() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-x", "2", "-b", "exp"])
+        .assert()
+        .success();
+    std::fs::write(repo.worktree_path_for("exp-2").join("notes.txt"), "draft").unwrap();
+
+    repo.command()
+        .args(["rm", "--group", "exp"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("refusing to remove group 'exp'"))
+        .stderr(predicate::str::contains("exp-2: 1 untracked file(s)"));
+    assert!(repo.worktree_path_for("exp-1").exists());
+    assert!(repo.worktree_path_for("exp-2").exists());
+}
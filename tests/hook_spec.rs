@@ -3,6 +3,7 @@ use common::TestRepo;
 use std::fs;
 
 #[test]
+#[allow(clippy::needless_borrows_for_generic_args)]
 fn glob_copy_hook_copies_files() {
     let repo = TestRepo::new();
 
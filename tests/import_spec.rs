@@ -0,0 +1,116 @@
+mod common;
+
+use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
+use std::fs;
+use std::path::PathBuf;
+
+// git で直接 base_dir の外に worktree を作る
+fn add_unmanaged(repo: &TestRepo, dir: &str, branch: &str) -> PathBuf {
+    let path = repo.path().join("elsewhere").join(dir);
+    repo.git(&["worktree", "add", "-q", "-b", branch, path.to_str().unwrap()]);
+    path
+}
+
+#[test]
+fn import_moves_worktree_into_layout() {
+    let repo = TestRepo::new();
+    let source = add_unmanaged(&repo, "feat", "feature/imported");
+    fs::write(source.join("notes.txt"), "keep me").unwrap();
+
+    repo.command()
+        .args(["import", source.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Imported 'feature/imported'"));
+
+    let destination = repo.worktree_path_for("feature/imported");
+    assert!(!source.exists());
+    assert_eq!(
+        fs::read_to_string(destination.join("notes.txt")).unwrap(),
+        "keep me"
+    );
+
+    repo.command()
+        .args(["cd", "feature/imported"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            normalize_path(&destination).to_str().unwrap(),
+        ));
+}
+
+#[test]
+fn import_all_moves_every_unmanaged_worktree() {
+    let repo = TestRepo::new();
+    let first = add_unmanaged(&repo, "one", "feature/one");
+    let second = add_unmanaged(&repo, "two", "feature/two");
+    repo.command().args(["add", "-b", "feature/managed"]).assert().success();
+
+    repo.command()
+        .args(["import", "--all", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Importing 2 worktree(s):"))
+        .stdout(predicate::str::contains("feature/managed").not());
+    assert!(first.exists() && second.exists());
+
+    repo.command().args(["import", "--all"]).assert().success();
+    assert!(repo.worktree_path_for("feature/one").exists());
+    assert!(repo.worktree_path_for("feature/two").exists());
+
+    repo.command()
+        .args(["import", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No unmanaged worktrees to import"));
+}
+
+#[test]
+fn import_rejects_worktrees_already_in_layout() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/managed"]).assert().success();
+    let path = repo.worktree_path_for("feature/managed");
+
+    repo.command()
+        .args(["import", path.to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already in gwe's layout"));
+
+    repo.command()
+        .args(["import", repo.path().to_str().unwrap()])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot import the main worktree"));
+}
+
+#[test]
+fn extra_dirs_are_treated_as_managed_without_moving() {
+    let repo = TestRepo::new();
+    let source = add_unmanaged(&repo, "feat", "feature/extra");
+
+    repo.command()
+        .args(["rm", "feature/extra"])
+        .assert()
+        .failure();
+
+    repo.set_config("gwe.worktrees.extraDirs", "elsewhere");
+
+    repo.command()
+        .args(["import", "--all"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No unmanaged worktrees to import"));
+
+    repo.command()
+        .args(["cd", "feature/extra"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            normalize_path(&source).to_str().unwrap(),
+        ));
+
+    repo.command().args(["rm", "feature/extra"]).assert().success();
+    assert!(!source.exists());
+}
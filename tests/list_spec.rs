@@ -132,3 +132,77 @@ fn list_json_reflects_current_and_main_flags() {
     assert!(feature.is_current);
 }
 
+
+#[derive(Debug, Deserialize)]
+struct DivergenceEntry {
+    branch: Option<String>,
+    upstream_gone: bool,
+    ahead: Option<usize>,
+    behind: Option<usize>,
+    base: Option<String>,
+    base_ahead: Option<usize>,
+    base_behind: Option<usize>,
+}
+
+fn commit_file(dir: &Path, name: &str) {
+    std::fs::write(dir.join(name), name).unwrap();
+    common::run_git(dir, &["add", name]);
+    common::run_git(dir, &["commit", "-q", "-m", name]);
+}
+
+#[test]
+fn list_reports_ahead_behind_against_upstream_and_default_branch() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/counts"]).assert().success();
+    let worktree_path = repo.worktree_path_for("feature/counts");
+    common::run_git(&worktree_path, &["branch", "--set-upstream-to=main"]);
+    commit_file(&worktree_path, "one.txt");
+    commit_file(&worktree_path, "two.txt");
+    commit_file(repo.path(), "main.txt");
+
+    let output = repo.command().args(["list", "--json"]).output().unwrap();
+    let entries: Vec<DivergenceEntry> = serde_json::from_slice(&output.stdout).unwrap();
+    let feature = entries
+        .iter()
+        .find(|e| e.branch.as_deref() == Some("feature/counts"))
+        .unwrap();
+    assert!(!feature.upstream_gone);
+    assert_eq!((feature.ahead, feature.behind), (Some(2), Some(1)));
+    assert_eq!(feature.base.as_deref(), Some("main"));
+    assert_eq!((feature.base_ahead, feature.base_behind), (Some(2), Some(1)));
+    let main = entries.iter().find(|e| e.branch.as_deref() == Some("main")).unwrap();
+    assert_eq!((main.ahead, main.behind), (None, None));
+    assert_eq!((main.base_ahead, main.base_behind), (Some(0), Some(0)));
+
+    let table = String::from_utf8(repo.command().arg("list").output().unwrap().stdout).unwrap();
+    assert!(table.contains("AHEAD/BEHIND") && table.contains("BASE"));
+    let row = table.lines().find(|line| line.contains("feature/counts")).unwrap();
+    assert!(row.contains("+2/-1"), "row should show divergence: {}", row);
+}
+
+#[test]
+fn list_reports_gone_upstream() {
+    let repo = TestRepo::new();
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    let remote = remote_dir.path().join("remote.git");
+    common::run_git(remote_dir.path(), &["init", "--bare", "-q", &remote.to_string_lossy()]);
+    repo.git(&["remote", "add", "origin", &remote.to_string_lossy()]);
+    repo.command().args(["add", "-b", "feature/merged"]).assert().success();
+    let worktree_path = repo.worktree_path_for("feature/merged");
+    common::run_git(&worktree_path, &["push", "-q", "-u", "origin", "feature/merged"]);
+    common::run_git(&remote, &["branch", "-D", "feature/merged"]);
+    repo.git(&["fetch", "-q", "--prune", "origin"]);
+
+    let output = repo.command().args(["list", "--json"]).output().unwrap();
+    let entries: Vec<DivergenceEntry> = serde_json::from_slice(&output.stdout).unwrap();
+    let feature = entries
+        .iter()
+        .find(|e| e.branch.as_deref() == Some("feature/merged"))
+        .unwrap();
+    assert!(feature.upstream_gone);
+    assert_eq!(feature.ahead, None);
+
+    let table = String::from_utf8(repo.command().arg("list").output().unwrap().stdout).unwrap();
+    let row = table.lines().find(|line| line.contains("feature/merged")).unwrap();
+    assert!(row.contains("gone"), "row should mark the upstream as gone: {}", row);
+}
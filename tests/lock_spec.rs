@@ -0,0 +1,103 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+
+#[test]
+fn lock_shows_reason_in_list_and_unlock_clears_it() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/agent"]).assert().success();
+
+    repo.command()
+        .args(["lock", "feature/agent", "--reason", "long-running agent"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked 'feature/agent'"));
+
+    repo.command()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("clean, locked: long-running agent"));
+
+    let output = repo.command().args(["list", "--json"]).output().unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let entry = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["branch"] == "feature/agent")
+        .unwrap();
+    assert_eq!(entry["locked"], true);
+    assert_eq!(entry["lock_reason"], "long-running agent");
+
+    repo.command()
+        .args(["lock", "feature/agent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already locked (long-running agent)"));
+
+    repo.command().args(["unlock", "feature/agent"]).assert().success();
+    repo.command()
+        .args(["unlock", "feature/agent"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not locked"));
+
+    let output = repo.command().args(["list", "--json"]).output().unwrap();
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(entries.as_array().unwrap().iter().all(|entry| entry["locked"] == false));
+}
+
+#[test]
+fn rm_refuses_locked_worktree_unless_forced_twice() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["add", "-b", "feature/locked", "--lock=agent"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Locked 'feature/locked'"));
+    let path = repo.worktree_path_for("feature/locked");
+
+    repo.command()
+        .args(["rm", "feature/locked"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("it is locked (agent)"))
+        .stderr(predicate::str::contains("-ff"));
+    repo.command()
+        .args(["rm", "-f", "feature/locked"])
+        .assert()
+        .failure();
+    assert!(path.exists());
+
+    repo.command()
+        .args(["rm", "-ff", "feature/locked"])
+        .assert()
+        .success();
+    assert!(!path.exists());
+}
+
+#[test]
+fn prune_skips_locked_worktrees_unless_forced_twice() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/done"]).assert().success();
+    repo.command()
+        .args(["lock", "feature/done", "--reason", "keep"])
+        .assert()
+        .success();
+    let path = repo.worktree_path_for("feature/done");
+
+    repo.command()
+        .args(["prune", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip (locked (keep))"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["prune", "--merged", "--yes", "-ff"])
+        .assert()
+        .success();
+    assert!(!path.exists());
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{TestRepo, branch_exists, git_stdout, normalize_path};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn mv_renames_branch_and_moves_worktree() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/old"]).assert().success();
+    let old_path = repo.worktree_path_for("feature/old");
+    fs::write(old_path.join("wip.txt"), "work in progress").unwrap();
+
+    repo.command()
+        .args(["mv", "feature/old", "feature/new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Renamed branch 'feature/old' to 'feature/new'"));
+
+    let new_path = repo.worktree_path_for("feature/new");
+    assert!(!old_path.exists());
+    assert_eq!(
+        fs::read_to_string(new_path.join("wip.txt")).unwrap(),
+        "work in progress"
+    );
+    assert!(!branch_exists(repo.path(), "feature/old"));
+    assert_eq!(git_stdout(&new_path, &["branch", "--show-current"]), "feature/new");
+
+    repo.command()
+        .args(["cd", "feature/new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(normalize_path(&new_path).to_str().unwrap()));
+}
+
+#[test]
+fn mv_rejects_existing_or_invalid_branch_names() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/a"]).assert().success();
+    repo.create_branch("feature/taken");
+
+    repo.command()
+        .args(["mv", "feature/a", "feature/taken"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("branch 'feature/taken' already exists"));
+
+    repo.command()
+        .args(["mv", "feature/a", "bad..name"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("'bad..name' is not a valid branch name"));
+
+    repo.set_config("gwe.branch.prefix", "feature/");
+    repo.command()
+        .args(["mv", "feature/a", "misc/a"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must start with one of: feature/"));
+
+    assert!(repo.worktree_path_for("feature/a").exists());
+}
+
+#[test]
+fn mv_rolls_back_branch_rename_when_move_fails() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/a"]).assert().success();
+    // 移動先の親ディレクトリがファイルなので移動に失敗する
+    fs::write(repo.worktree_path_for("blocked"), "not a directory").unwrap();
+
+    repo.command()
+        .args(["mv", "feature/a", "blocked/inner"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("rolled back"));
+
+    assert!(branch_exists(repo.path(), "feature/a"));
+    assert!(!branch_exists(repo.path(), "blocked/inner"));
+    assert_eq!(
+        git_stdout(&repo.worktree_path_for("feature/a"), &["branch", "--show-current"]),
+        "feature/a"
+    );
+}
+
+#[test]
+fn mv_remote_renames_upstream_branch() {
+    let repo = TestRepo::new();
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    let remote = remote_dir.path().join("remote.git");
+    common::run_git(remote_dir.path(), &["init", "--bare", "-q", &remote.to_string_lossy()]);
+    repo.git(&["remote", "add", "origin", &remote.to_string_lossy()]);
+    repo.git(&["push", "-q", "origin", "HEAD"]);
+    repo.command().args(["add", "-b", "feature/old"]).assert().success();
+    let old_path = repo.worktree_path_for("feature/old");
+    repo.run_in_worktree(&old_path, &["push", "-q", "-u", "origin", "feature/old"]);
+
+    repo.command()
+        .args(["mv", "--remote", "feature/old", "feature/new"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Renamed remote branch 'origin/feature/old' to 'origin/feature/new'",
+        ));
+
+    assert!(branch_exists(&remote, "feature/new"));
+    assert!(!branch_exists(&remote, "feature/old"));
+    assert_eq!(
+        git_stdout(
+            &repo.worktree_path_for("feature/new"),
+            &["rev-parse", "--abbrev-ref", "@{u}"]
+        ),
+        "origin/feature/new"
+    );
+}
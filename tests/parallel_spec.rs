@@ -0,0 +1,56 @@
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+
+#[cfg(unix)]
+#[test]
+fn multiplier_runs_hooks_in_parallel() {
+    let repo = TestRepo::new();
+    // 各フックは兄弟 2 つ分の開始マーカーが揃うまで待つ。順番に実行すると待ち切れずに失敗する
+    repo.set_config(
+        "gwe.hook.postcreate",
+        r#"touch "$GIT_GWE_WORKTREE_PATH.started"; i=0; while [ "$(ls "$(dirname "$GIT_GWE_WORKTREE_PATH")"/*.started | wc -l)" -lt 2 ] && [ $i -lt 100 ]; do sleep 0.1; i=$((i+1)); done; [ $i -lt 100 ]"#,
+    );
+
+    repo.command()
+        .args(["add", "-x", "2", "-j", "2", "-b", "exp"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[1/2] Created worktree 'exp-1'"))
+        .stdout(predicate::str::contains("[2/2] Created worktree 'exp-2'"))
+        .stdout(predicate::str::contains("[2/2] ✓ All hooks executed successfully"));
+}
+
+#[cfg(unix)]
+#[test]
+fn multiplier_reports_failed_siblings() {
+    let repo = TestRepo::new();
+    repo.set_config(
+        "gwe.hook.postcreate",
+        r#"case "$GIT_GWE_WORKTREE_PATH" in *-2) exit 1;; esac"#,
+    );
+
+    repo.command()
+        .args(["add", "-x", "3", "-b", "exp"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_match(r"1/3\s+exp-1\s+ok").unwrap())
+        .stdout(predicate::str::is_match(r"2/3\s+exp-2\s+failed").unwrap())
+        .stdout(predicate::str::is_match(r"3/3\s+exp-3\s+ok").unwrap())
+        .stderr(predicate::str::contains("1 of 3 worktrees could not be created"));
+
+    assert!(repo.worktree_path_for("exp-3").exists());
+}
+
+#[test]
+fn siblings_detect_paths_colliding_with_each_other() {
+    let repo = TestRepo::new();
+
+    repo.command()
+        .args(["add", "-b", "try/login", "-b", "Try/Login"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("map to the same worktree path"))
+        .stderr(predicate::str::contains("1 of 2 worktrees could not be created"));
+}
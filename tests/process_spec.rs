@@ -0,0 +1,147 @@
+#![cfg(target_os = "linux")]
+
+mod common;
+
+use common::TestRepo;
+use predicates::prelude::*;
+use std::path::Path;
+use std::process::{Child, Command};
+
+fn spawn_sleep(dir: &Path) -> Child {
+    Command::new("sleep")
+        .arg("30")
+        .current_dir(dir)
+        .spawn()
+        .expect("spawn sleep")
+}
+
+// 子プロセスが `file` を開くまで待つ
+fn wait_until_open(child: &Child, file: &Path) {
+    let file = file.canonicalize().unwrap();
+    for _ in 0..50 {
+        let opened = std::fs::read_dir(format!("/proc/{}/fd", child.id()))
+            .map(|fds| {
+                fds.flatten()
+                    .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|target| target == file))
+            })
+            .unwrap_or(false);
+        if opened {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+}
+
+fn stop(mut child: Child) {
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[test]
+fn rm_refuses_while_a_process_runs_inside() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/busy");
+    repo.command().args(["add", "-b", "feature/busy"]).assert().success();
+    let child = spawn_sleep(&path);
+    let pid = child.id().to_string();
+
+    let result = repo.command().args(["rm", "--force", "feature/busy"]).assert().failure();
+    stop(child);
+    result.stderr(
+        predicate::str::contains("processes are running inside it")
+            .and(predicate::str::contains(format!("{} (sleep 30)", pid)))
+            .and(predicate::str::contains("--kill")),
+    );
+    assert!(path.exists());
+}
+
+#[test]
+fn rm_kill_terminates_processes_before_removing() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/busy");
+    repo.command().args(["add", "-b", "feature/busy"]).assert().success();
+    let mut child = spawn_sleep(&path);
+
+    repo.command()
+        .args(["rm", "--kill", "feature/busy"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("Stopping processes in 'feature/busy'")
+                .and(predicate::str::contains("Removed worktree")),
+        );
+    assert!(child.try_wait().unwrap().is_some(), "sleep was terminated");
+    assert!(!path.exists());
+}
+
+#[test]
+fn rm_detects_open_files_inside_the_worktree() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/open");
+    repo.command().args(["add", "-b", "feature/open"]).assert().success();
+    std::fs::write(path.join("log.txt"), "").unwrap();
+    let tail = Command::new("tail")
+        .args(["-f", path.join("log.txt").to_str().unwrap()])
+        .current_dir(repo.path())
+        .spawn()
+        .expect("spawn tail");
+    wait_until_open(&tail, &path.join("log.txt"));
+
+    let result = repo.command().args(["rm", "--force", "feature/open"]).assert().failure();
+    stop(tail);
+    result.stderr(predicate::str::contains("tail -f"));
+}
+
+#[test]
+fn list_marks_worktrees_in_use() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/busy");
+    repo.command().args(["add", "-b", "feature/busy"]).assert().success();
+    repo.command().args(["add", "-b", "feature/idle"]).assert().success();
+    let child = spawn_sleep(&path);
+
+    let output = repo.command().args(["list", "--json"]).output().unwrap();
+    let table = repo.command().arg("list").output().unwrap();
+    stop(child);
+
+    let entries: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let in_use = |branch: &str| {
+        entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["branch"] == branch)
+            .map(|entry| entry["in_use"].as_bool().unwrap())
+            .unwrap()
+    };
+    assert!(in_use("feature/busy"));
+    assert!(!in_use("feature/idle"));
+    assert!(!in_use("main"), "processes in nested worktrees are not counted for main");
+
+    let table = String::from_utf8_lossy(&table.stdout);
+    let busy_line = table.lines().find(|line| line.contains("feature/busy")).unwrap();
+    assert!(busy_line.contains("clean, in use"));
+}
+
+#[test]
+fn prune_skips_worktrees_in_use_unless_kill() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/done");
+    repo.command().args(["add", "-b", "feature/done"]).assert().success();
+    let mut child = spawn_sleep(&path);
+
+    repo.command()
+        .args(["prune", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip (in use by"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["prune", "--merged", "--yes", "--kill"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Pruned 1 worktree(s)"));
+    assert!(child.try_wait().unwrap().is_some());
+    assert!(!path.exists());
+}
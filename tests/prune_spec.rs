@@ -0,0 +1,136 @@
+mod common;
+
+use common::{TestRepo, branch_exists};
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+
+fn commit_in(repo: &TestRepo, path: &Path, file: &str) {
+    fs::write(path.join(file), file).unwrap();
+    repo.run_in_worktree(path, &["add", file]);
+    repo.run_in_worktree(path, &["commit", "-q", "-m", file]);
+}
+
+#[test]
+fn prune_merged_removes_only_merged_worktrees() {
+    let repo = TestRepo::new();
+    let done = repo.worktree_path_for("feature/done");
+    let wip = repo.worktree_path_for("feature/wip");
+    repo.command().args(["add", "-b", "feature/done"]).assert().success();
+    repo.command().args(["add", "-b", "feature/wip"]).assert().success();
+    commit_in(&repo, &done, "done.txt");
+    commit_in(&repo, &wip, "wip.txt");
+    repo.git(&["merge", "-q", "--ff-only", "feature/done"]);
+
+    repo.command()
+        .args(["prune", "--merged", "--yes", "-b"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("merged into main"))
+        .stdout(predicate::str::contains("Pruned 1 worktree(s)"));
+
+    assert!(!done.exists());
+    assert!(!branch_exists(repo.path(), "feature/done"));
+    assert!(wip.exists());
+    assert!(branch_exists(repo.path(), "feature/wip"));
+}
+
+#[test]
+fn prune_never_removes_dirty_worktrees_silently() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/dirty");
+    repo.command().args(["add", "-b", "feature/dirty"]).assert().success();
+    fs::write(path.join("notes.txt"), "draft").unwrap();
+
+    repo.command()
+        .args(["prune", "--merged", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip (would lose 1 untracked file(s))"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["prune", "--merged", "--yes", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("remove (to trash)"))
+        .stdout(predicate::str::contains("Saved to trash as"));
+    assert!(!path.exists());
+}
+
+#[test]
+fn prune_asks_for_confirmation_and_supports_dry_run() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/done");
+    repo.command().args(["add", "-b", "feature/done"]).assert().success();
+    commit_in(&repo, &path, "done.txt");
+    repo.git(&["merge", "-q", "--ff-only", "feature/done"]);
+
+    repo.command()
+        .args(["prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/done"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["prune"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --yes"));
+    assert!(path.exists());
+}
+
+#[test]
+fn prune_keeps_freshly_created_branches_by_default() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("agent/fresh");
+    repo.command().args(["add", "-b", "agent/fresh"]).assert().success();
+
+    repo.command()
+        .args(["prune", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("agent/fresh").not());
+
+    assert!(path.exists());
+    assert!(branch_exists(repo.path(), "agent/fresh"));
+}
+
+#[test]
+fn prune_prunable_and_gone_worktrees() {
+    let repo = TestRepo::new();
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    let remote = remote_dir.path().join("remote.git");
+    common::run_git(remote_dir.path(), &["init", "--bare", "-q", &remote.to_string_lossy()]);
+    repo.git(&["remote", "add", "origin", &remote.to_string_lossy()]);
+
+    let missing = repo.worktree_path_for("feature/missing");
+    let gone = repo.worktree_path_for("feature/gone");
+    let kept = repo.worktree_path_for("feature/kept");
+    for branch in ["feature/missing", "feature/gone", "feature/kept"] {
+        repo.command().args(["add", "-b", branch]).assert().success();
+    }
+    commit_in(&repo, &gone, "gone.txt");
+    commit_in(&repo, &kept, "kept.txt");
+    repo.run_in_worktree(&gone, &["push", "-q", "-u", "origin", "feature/gone"]);
+    repo.git(&["push", "-q", "origin", "--delete", "feature/gone"]);
+    repo.git(&["fetch", "-q", "--prune", "origin"]);
+    fs::remove_dir_all(&missing).unwrap();
+
+    repo.command()
+        .args(["prune", "--prunable", "--gone", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("prunable"))
+        .stdout(predicate::str::contains("upstream gone"))
+        .stdout(predicate::str::contains("Pruned 2 worktree(s)"));
+
+    assert!(!gone.exists());
+    assert!(kept.exists());
+    repo.command()
+        .args(["list", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("feature/missing").not());
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
+use std::path::PathBuf;
+
+// base_dir を `moved` に変えた後の worktree のパス
+fn moved_path(repo: &TestRepo, branch: &str) -> PathBuf {
+    let mut path = repo.path().join("moved");
+    path.push(repo.path().file_name().unwrap());
+    for segment in branch.split('/') {
+        path.push(segment);
+    }
+    path
+}
+
+fn git_supports_relative_paths() -> bool {
+    let output = std::process::Command::new("git").arg("version").output().unwrap();
+    let version = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .trim_start_matches("git version ")
+        .to_string();
+    let mut parts = version.split('.').map(|part| part.parse::<u32>().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0)) >= (2, 48)
+}
+
+#[test]
+fn relocate_moves_worktrees_after_base_dir_change() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/one"]).assert().success();
+    repo.command().args(["add", "-b", "two"]).assert().success();
+    let old = repo.worktree_path_for("feature/one");
+
+    repo.set_config("gwe.worktrees.dir", "moved");
+
+    repo.command()
+        .args(["relocate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Relocating 2 worktree(s):"));
+    assert!(old.exists());
+
+    repo.command()
+        .args(["relocate", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Moved 'feature/one'"));
+
+    assert!(!old.exists());
+    assert!(!repo.worktrees_dir().join(repo.path().file_name().unwrap()).exists());
+    let moved = moved_path(&repo, "feature/one");
+    assert!(moved.join("README.md").exists());
+    assert!(moved_path(&repo, "two").exists());
+
+    repo.command()
+        .args(["cd", "feature/one"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(normalize_path(&moved).to_str().unwrap()));
+
+    repo.command()
+        .arg("relocate")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("All managed worktrees are already in place"));
+}
+
+#[test]
+fn relocate_uses_from_for_detached_worktrees_and_skips_locked() {
+    let repo = TestRepo::new();
+    repo.git(&["tag", "v1"]);
+    repo.command().args(["add", "--detach", "v1"]).assert().success();
+    repo.command().args(["add", "-b", "feature/locked"]).assert().success();
+    let locked = repo.worktree_path_for("feature/locked");
+    repo.git(&["worktree", "lock", locked.to_str().unwrap()]);
+
+    repo.set_config("gwe.worktrees.dir", "moved");
+
+    repo.command()
+        .args(["relocate", "--yes", "--from", "worktree"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skip: locked"));
+
+    assert!(moved_path(&repo, "v1").join("README.md").exists());
+    assert!(locked.exists());
+}
+
+#[test]
+fn relocate_can_switch_to_relative_gitdir_links() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/rel"]).assert().success();
+
+    let assert = repo.command().args(["relocate", "--relative-paths"]).assert();
+    if git_supports_relative_paths() {
+        assert
+            .success()
+            .stdout(predicate::str::contains("Switched worktrees to relative gitdir links"));
+        let dot_git =
+            std::fs::read_to_string(repo.worktree_path_for("feature/rel").join(".git")).unwrap();
+        assert!(!dot_git.contains(repo.path().to_str().unwrap()));
+    } else {
+        assert
+            .failure()
+            .stderr(predicate::str::contains("relative gitdir links require git 2.48"));
+    }
+}
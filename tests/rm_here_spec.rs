@@ -0,0 +1,108 @@
+mod common;
+
+use common::{TestRepo, normalize_path};
+use predicates::prelude::*;
+use std::path::PathBuf;
+
+fn stdout_path(output: &std::process::Output) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+#[test]
+fn rm_here_removes_current_worktree_and_prints_main() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/here");
+    repo.command().args(["add", "-b", "feature/here"]).assert().success();
+
+    let output = repo.command_in(&path).args(["rm", "--here"]).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(normalize_path(&stdout_path(&output)), normalize_path(repo.path()));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Removed worktree"));
+    assert!(!path.exists());
+}
+
+#[test]
+fn rm_dot_uses_configured_fallback() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/here");
+    let fallback = repo.worktree_path_for("feature/home");
+    repo.command().args(["add", "-b", "feature/here"]).assert().success();
+    repo.command().args(["add", "-b", "feature/home"]).assert().success();
+    repo.set_config("gwe.rm.fallback", "feature/home");
+
+    let output = repo.command_in(&path).args(["rm", "."]).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(normalize_path(&stdout_path(&output)), normalize_path(&fallback));
+    assert!(!path.exists());
+}
+
+#[test]
+fn rm_here_keeps_the_safety_checks() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/here");
+    repo.command().args(["add", "-b", "feature/here"]).assert().success();
+    std::fs::write(path.join("notes.txt"), "draft").unwrap();
+
+    repo.command_in(&path)
+        .args(["rm", "--here"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("1 untracked file(s)"));
+    assert!(path.exists());
+
+    repo.command()
+        .args(["rm", "--here"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot remove the main worktree"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn rm_here_refuses_while_processes_run_inside() {
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/busy");
+    repo.command().args(["add", "-b", "feature/busy"]).assert().success();
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .current_dir(&path)
+        .spawn()
+        .unwrap();
+
+    let result = repo.command_in(&path).args(["rm", "--here"]).assert().failure();
+    let _ = child.kill();
+    let _ = child.wait();
+    result.stderr(predicate::str::contains("processes are running inside it"));
+    assert!(path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn bash_wrapper_moves_shell_after_rm_here() {
+    if std::process::Command::new("bash").arg("--version").output().is_err() {
+        return;
+    }
+    let repo = TestRepo::new();
+    let path = repo.worktree_path_for("feature/here");
+    repo.command().args(["add", "-b", "feature/here"]).assert().success();
+
+    let exe = PathBuf::from(env!("CARGO_BIN_EXE_gwe"));
+    let bin_dir = exe.parent().unwrap();
+    let script = "eval \"$(command gwe shell-init bash)\"\ngwe rm --here >/dev/null 2>&1 || exit 1\npwd -P\n";
+    let output = std::process::Command::new("bash")
+        .arg("-c")
+        .arg(script)
+        .current_dir(&path)
+        .env(
+            "PATH",
+            format!("{}:{}", bin_dir.display(), std::env::var("PATH").unwrap_or_default()),
+        )
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(normalize_path(&stdout_path(&output)), normalize_path(repo.path()));
+    assert!(!path.exists());
+}
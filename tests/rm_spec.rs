@@ -1,8 +1,7 @@
 mod common;
 
-use common::TestRepo;
+use common::{TestRepo, branch_exists};
 use predicates::prelude::*;
-use std::path::Path;
 
 #[test]
 fn rm_with_branch_deletes_branch_and_worktree() {
@@ -70,7 +69,7 @@ fn rm_rejects_current_worktree() {
 }
 
 #[test]
-fn rm_implies_force_branch() {
+fn rm_refuses_unmerged_branch_without_force() {
     let repo = TestRepo::new();
     let branch = "feature/unmerged";
     let worktree_path = repo.worktree_path_for(branch);
@@ -83,20 +82,45 @@ fn rm_implies_force_branch() {
     repo.run_in_worktree(&worktree_path, &["add", "unmerged.txt"]);
     repo.run_in_worktree(&worktree_path, &["commit", "-m", "unmerged commit"]);
 
-    // Now branch is unmerged. "git branch -d" should fail, but "gwe rm -b" should succeed.
+    // Deleting the branch would lose the commit, so plain "gwe rm -b" refuses.
     repo.command()
         .args(["rm", "-b", branch])
         .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 commit(s) not merged into 'main'"))
+        .stderr(predicate::str::contains("--force"));
+    assert!(worktree_path.exists(), "worktree must remain after refusal");
+    assert!(branch_exists(repo.path(), branch));
+
+    // Keeping the branch loses nothing.
+    repo.command().args(["rm", branch]).assert().success();
+    assert!(branch_exists(repo.path(), branch));
+}
+
+#[test]
+fn rm_force_deletes_unmerged_branch() {
+    let repo = TestRepo::new();
+    let branch = "feature/unmerged";
+    let worktree_path = repo.worktree_path_for(branch);
+
+    repo.git(&["worktree", "add", "-b", branch, &worktree_path.to_string_lossy(), "HEAD"]);
+    std::fs::write(worktree_path.join("unmerged.txt"), "unmerged content").unwrap();
+    repo.run_in_worktree(&worktree_path, &["add", "unmerged.txt"]);
+    repo.run_in_worktree(&worktree_path, &["commit", "-m", "unmerged commit"]);
+
+    repo.command()
+        .args(["rm", "-b", "--force", branch])
+        .assert()
         .success();
 
     assert!(
         !branch_exists(repo.path(), branch),
-        "branch should be deleted even if unmerged when -b is used"
+        "branch should be deleted even if unmerged when --force is used"
     );
 }
 
 #[test]
-fn rm_implies_force_worktree() {
+fn rm_refuses_dirty_worktree_without_force() {
     let repo = TestRepo::new();
     let branch = "feature/dirty";
     let worktree_path = repo.worktree_path_for(branch);
@@ -107,29 +131,231 @@ fn rm_implies_force_worktree() {
     // Make worktree dirty
     std::fs::write(worktree_path.join("dirty.txt"), "dirty content").unwrap();
 
-    // "git worktree remove" without --force should fail, but "gwe rm" should succeed.
+    std::fs::write(worktree_path.join("README.md"), "changed").unwrap();
+
     repo.command()
         .args(["rm", branch])
         .assert()
+        .failure()
+        .stderr(predicate::str::contains("refusing to remove 'feature/dirty'"))
+        .stderr(predicate::str::contains("1 file(s) with uncommitted changes"))
+        .stderr(predicate::str::contains("1 untracked file(s)"));
+    assert!(worktree_path.exists(), "dirty worktree must remain");
+
+    repo.command()
+        .args(["rm", "-f", branch])
+        .assert()
         .success();
 
     assert!(
         !worktree_path.exists(),
-        "worktree should be removed even if dirty"
+        "worktree should be removed when --force is used"
+    );
+}
+
+#[test]
+fn rm_force_config_restores_old_behaviour() {
+    let repo = TestRepo::new();
+    let branch = "feature/dirty";
+    let worktree_path = repo.worktree_path_for(branch);
+
+    repo.git(&["worktree", "add", "-b", branch, &worktree_path.to_string_lossy(), "HEAD"]);
+    std::fs::write(worktree_path.join("dirty.txt"), "dirty content").unwrap();
+    repo.set_config("gwe.rm.force", "true");
+
+    repo.command().args(["rm", branch]).assert().success();
+    assert!(!worktree_path.exists());
+}
+
+#[test]
+fn rm_refuses_commits_missing_from_remotes() {
+    let repo = TestRepo::new();
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    let remote = remote_dir.path().join("remote.git");
+    common::run_git(remote_dir.path(), &["init", "--bare", "-q", &remote.to_string_lossy()]);
+    repo.git(&["remote", "add", "origin", &remote.to_string_lossy()]);
+    repo.git(&["push", "-q", "origin", "HEAD"]);
+
+    let branch = "feature/local";
+    let worktree_path = repo.worktree_path_for(branch);
+    repo.git(&["worktree", "add", "-b", branch, &worktree_path.to_string_lossy(), "HEAD"]);
+    std::fs::write(worktree_path.join("local.txt"), "local").unwrap();
+    repo.run_in_worktree(&worktree_path, &["add", "local.txt"]);
+    repo.run_in_worktree(&worktree_path, &["commit", "-m", "local commit"]);
+    repo.git(&["merge", "-q", "--ff-only", branch]);
+
+    repo.command()
+        .args(["rm", "-b", branch])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("1 commit(s) not pushed to any remote"))
+        .stderr(predicate::str::contains("not merged").not());
+
+    repo.git(&["push", "-q", "origin", branch]);
+    repo.command().args(["rm", "-b", branch]).assert().success();
+    assert!(!branch_exists(repo.path(), branch));
+}
+
+// ブランチを push して追跡させたリポジトリと、ベアリモートのパス
+fn repo_with_pushed_branch(branch: &str) -> (TestRepo, tempfile::TempDir, std::path::PathBuf) {
+    let repo = TestRepo::new();
+    let remote_dir = tempfile::TempDir::new().unwrap();
+    let remote = remote_dir.path().join("remote.git");
+    common::run_git(remote_dir.path(), &["init", "--bare", "-q", &remote.to_string_lossy()]);
+    repo.git(&["remote", "add", "origin", &remote.to_string_lossy()]);
+    repo.git(&["push", "-q", "origin", "HEAD"]);
+
+    let worktree_path = repo.worktree_path_for(branch);
+    repo.git(&["worktree", "add", "-b", branch, &worktree_path.to_string_lossy(), "HEAD"]);
+    repo.run_in_worktree(&worktree_path, &["push", "-q", "-u", "origin", branch]);
+    (repo, remote_dir, remote)
+}
+
+#[test]
+fn rm_remote_deletes_upstream_branch() {
+    let branch = "feature/finished";
+    let (repo, _remote_dir, remote) = repo_with_pushed_branch(branch);
+
+    repo.command()
+        .args(["rm", "-b", "--remote", branch])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed remote branch 'origin/feature/finished'"));
+    assert!(!branch_exists(repo.path(), branch));
+    assert!(!branch_exists(&remote, branch));
+}
+
+#[test]
+fn rm_remote_requires_with_branch() {
+    let (repo, _remote_dir, remote) = repo_with_pushed_branch("feature/finished");
+
+    repo.command()
+        .args(["rm", "--remote", "feature/finished"])
+        .assert()
+        .failure();
+    assert!(branch_exists(&remote, "feature/finished"));
+}
+
+#[test]
+fn rm_remote_refuses_when_remote_has_commits_missing_locally() {
+    let branch = "feature/shared";
+    let (repo, remote_dir, remote) = repo_with_pushed_branch(branch);
+
+    // 別のクローンから同じブランチへコミットを push する
+    let other = remote_dir.path().join("other");
+    common::run_git(
+        remote_dir.path(),
+        &["clone", "-q", "-b", branch, &remote.to_string_lossy(), &other.to_string_lossy()],
+    );
+    std::fs::write(other.join("theirs.txt"), "theirs").unwrap();
+    common::run_git(&other, &["add", "theirs.txt"]);
+    common::run_git(
+        &other,
+        &[
+            "-c",
+            "user.name=Other",
+            "-c",
+            "user.email=other@example.com",
+            "commit",
+            "-q",
+            "-m",
+            "theirs",
+        ],
     );
+    common::run_git(&other, &["push", "-q", "origin", branch]);
+
+    repo.command()
+        .args(["rm", "-b", "--remote", branch])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "refusing to delete remote branch 'origin/feature/shared': it has 1 commit(s) not present locally",
+        ));
+    assert!(branch_exists(repo.path(), branch), "nothing is removed when refused");
+    assert!(branch_exists(&remote, branch));
+
+    repo.command()
+        .args(["rm", "-b", "--remote", "--force", branch])
+        .assert()
+        .success();
+    assert!(!branch_exists(&remote, branch));
+}
+
+#[test]
+fn rm_remote_skips_branches_already_deleted_on_remote() {
+    let branch = "feature/merged";
+    let (repo, _remote_dir, remote) = repo_with_pushed_branch(branch);
+    common::run_git(&remote, &["branch", "-D", branch]);
+
+    repo.command()
+        .args(["rm", "-b", "--remote", branch])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed remote branch").not());
+    assert!(!branch_exists(repo.path(), branch));
 }
 
-fn branch_exists(repo_path: &Path, branch: &str) -> bool {
-    std::process::Command::new("git")
-        .current_dir(repo_path)
-        .args([
-            "show-ref",
-            "--verify",
-            "--quiet",
-            &format!("refs/heads/{branch}"),
-        ])
-        .status()
-        .expect("git show-ref")
+
+#[test]
+fn rm_accepts_several_targets_and_patterns() {
+    let repo = TestRepo::new();
+    for branch in ["exp-1", "exp-2", "feature/old", "feature/keep"] {
+        repo.command().args(["add", "-b", branch]).assert().success();
+    }
+
+    repo.command()
+        .args(["rm", "exp-*", "feature/old"])
+        .assert()
         .success()
+        .stdout(predicate::str::contains("Matched 3 worktree(s):"))
+        .stdout(predicate::str::contains("Summary: 3 removed, 0 skipped, 0 failed"));
+
+    for branch in ["exp-1", "exp-2", "feature/old"] {
+        assert!(!repo.worktree_path_for(branch).exists(), "{branch} should be removed");
+    }
+    assert!(repo.worktree_path_for("feature/keep").exists());
 }
 
+#[test]
+fn rm_keeps_going_past_failures() {
+    let repo = TestRepo::new();
+    for branch in ["exp-1", "exp-2", "exp-3"] {
+        repo.command().args(["add", "-b", branch]).assert().success();
+    }
+    std::fs::write(repo.worktree_path_for("exp-2").join("notes.txt"), "draft").unwrap();
+
+    repo.command_in(&repo.worktree_path_for("exp-3"))
+        .args(["rm", "exp-*", "missing", "nomatch-*"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("Summary: 1 removed, 1 skipped, 3 failed"))
+        .stdout(predicate::str::contains("✓ exp-1  removed"))
+        .stdout(predicate::str::contains("- exp-3  skipped (current worktree)"))
+        .stdout(predicate::str::contains("✗ exp-2  failed (refusing to remove 'exp-2'; it would lose:)"))
+        .stdout(predicate::str::contains("      - 1 untracked file(s)"))
+        .stdout(predicate::str::contains("✗ missing  failed (worktree 'missing' not found)"))
+        .stdout(predicate::str::contains("✗ nomatch-*  failed (no worktree matches 'nomatch-*')"))
+        .stderr(predicate::str::contains("3 of 5 target(s) could not be removed"));
+
+    assert!(!repo.worktree_path_for("exp-1").exists());
+    assert!(repo.worktree_path_for("exp-2").exists());
+    assert!(repo.worktree_path_for("exp-3").exists());
+}
+
+#[test]
+fn rm_removes_empty_parent_directories() {
+    let repo = TestRepo::new();
+    let auth = repo.worktree_path_for("feature/auth");
+    let other = repo.worktree_path_for("feature/other");
+    repo.command().args(["add", "-b", "feature/auth"]).assert().success();
+    repo.command().args(["add", "-b", "feature/other"]).assert().success();
+
+    repo.command().args(["rm", "feature/auth"]).assert().success();
+    assert!(auth.parent().unwrap().exists(), "feature/ still holds feature/other");
+
+    repo.command().args(["rm", "feature/other"]).assert().success();
+    let repo_dir = other.parent().unwrap().parent().unwrap();
+    assert!(!other.parent().unwrap().exists(), "empty feature/ is removed");
+    assert!(!repo_dir.exists(), "empty repository directory is removed");
+    assert!(repo.worktrees_dir().exists(), "base_dir itself is kept");
+}
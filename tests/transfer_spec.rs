@@ -0,0 +1,87 @@
+mod common;
+
+use common::{TestRepo, add_worktree, git_stdout};
+use predicates::prelude::*;
+use std::fs;
+
+#[test]
+fn transfer_moves_tracked_and_untracked_changes() {
+    let repo = TestRepo::new();
+    let from = add_worktree(&repo, "feature/from");
+    let to = add_worktree(&repo, "feature/to");
+    fs::write(from.join("README.md"), "fixed").unwrap();
+    fs::create_dir_all(from.join("src")).unwrap();
+    fs::write(from.join("src").join("new.rs"), "fn main() {}").unwrap();
+
+    repo.command()
+        .args(["transfer", "feature/from", "feature/to"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Moved 2 file(s)"));
+
+    assert_eq!(fs::read_to_string(to.join("README.md")).unwrap(), "fixed");
+    assert!(to.join("src").join("new.rs").exists());
+    assert_eq!(fs::read_to_string(from.join("README.md")).unwrap(), "hello world");
+    assert!(!from.join("src").join("new.rs").exists());
+    assert!(git_stdout(&from, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn transfer_moves_staged_renames() {
+    let repo = TestRepo::new();
+    let from = add_worktree(&repo, "feature/rename-from");
+    let to = add_worktree(&repo, "feature/rename-to");
+    common::run_git(&from, &["mv", "README.md", "GUIDE.md"]);
+
+    repo.command()
+        .args(["transfer", "feature/rename-from", "feature/rename-to"])
+        .assert()
+        .success();
+
+    assert!(!to.join("README.md").exists());
+    assert_eq!(fs::read_to_string(to.join("GUIDE.md")).unwrap(), "hello world");
+    assert_eq!(fs::read_to_string(from.join("README.md")).unwrap(), "hello world");
+    assert!(!from.join("GUIDE.md").exists());
+    assert!(git_stdout(&from, &["status", "--porcelain"]).is_empty());
+}
+
+#[test]
+fn transfer_copy_keeps_source_and_honours_paths() {
+    let repo = TestRepo::new();
+    let from = add_worktree(&repo, "feature/copy-from");
+    let to = add_worktree(&repo, "feature/copy-to");
+    fs::write(from.join("README.md"), "fixed").unwrap();
+    fs::write(from.join("other.txt"), "other").unwrap();
+
+    repo.command()
+        .args(["transfer", "--copy", "feature/copy-from", "feature/copy-to", "--", "README.md"])
+        .assert()
+        .success();
+
+    assert_eq!(fs::read_to_string(to.join("README.md")).unwrap(), "fixed");
+    assert!(!to.join("other.txt").exists());
+    assert_eq!(fs::read_to_string(from.join("README.md")).unwrap(), "fixed");
+}
+
+#[test]
+fn transfer_conflict_changes_nothing() {
+    let repo = TestRepo::new();
+    let from = add_worktree(&repo, "feature/conflict-from");
+    let to = add_worktree(&repo, "feature/conflict-to");
+    fs::write(to.join("README.md"), "diverged").unwrap();
+    common::run_git(&to, &["commit", "-q", "-am", "diverge"]);
+    fs::write(from.join("README.md"), "fixed").unwrap();
+    fs::write(from.join("new.txt"), "new").unwrap();
+
+    repo.command()
+        .args(["transfer", "feature/conflict-from", "feature/conflict-to"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("nothing was changed"))
+        .stderr(predicate::str::contains("README.md"));
+
+    assert_eq!(fs::read_to_string(from.join("README.md")).unwrap(), "fixed");
+    assert!(from.join("new.txt").exists());
+    assert!(!to.join("new.txt").exists());
+    assert!(git_stdout(&to, &["status", "--porcelain"]).is_empty());
+}
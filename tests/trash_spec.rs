@@ -0,0 +1,174 @@
+mod common;
+
+use common::{TestRepo, branch_exists};
+use predicates::prelude::*;
+use std::fs;
+
+fn trash_id(stdout: &[u8]) -> String {
+    let stdout = String::from_utf8_lossy(stdout);
+    let rest = stdout
+        .split("Saved to trash as ")
+        .nth(1)
+        .expect("trash message");
+    rest.split_whitespace().next().unwrap().to_string()
+}
+
+#[test]
+fn forced_rm_can_be_restored() {
+    let repo = TestRepo::new();
+    let branch = "feature/undo";
+    let path = repo.worktree_path_for(branch);
+    repo.command().args(["add", "-b", branch]).assert().success();
+
+    fs::write(path.join("committed.txt"), "keep me").unwrap();
+    repo.run_in_worktree(&path, &["add", "committed.txt"]);
+    repo.run_in_worktree(&path, &["commit", "-q", "-m", "work"]);
+    fs::write(path.join("README.md"), "edited").unwrap();
+    fs::create_dir_all(path.join("notes")).unwrap();
+    fs::write(path.join("notes/todo.txt"), "draft").unwrap();
+
+    let output = repo
+        .command()
+        .args(["rm", "-b", "--force", branch])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let id = trash_id(&output.stdout);
+    assert!(!path.exists());
+    assert!(!branch_exists(repo.path(), branch));
+
+    repo.command()
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&id))
+        .stdout(predicate::str::contains(branch));
+
+    repo.command()
+        .args(["restore", &id])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Restored feature/undo"));
+
+    assert!(branch_exists(repo.path(), branch));
+    assert_eq!(fs::read_to_string(path.join("committed.txt")).unwrap(), "keep me");
+    assert_eq!(fs::read_to_string(path.join("README.md")).unwrap(), "edited");
+    assert_eq!(fs::read_to_string(path.join("notes/todo.txt")).unwrap(), "draft");
+
+    repo.command()
+        .args(["trash", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Trash is empty"));
+}
+
+#[test]
+fn rm_without_losses_skips_the_trash() {
+    let repo = TestRepo::new();
+    repo.command().args(["add", "-b", "feature/clean"]).assert().success();
+
+    repo.command()
+        .args(["rm", "feature/clean"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved to trash").not());
+}
+
+#[test]
+fn restore_rejects_unknown_ids_and_occupied_paths() {
+    let repo = TestRepo::new();
+    repo.command()
+        .args(["restore", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("trash entry 'nope' not found"));
+
+    let branch = "feature/busy";
+    let path = repo.worktree_path_for(branch);
+    repo.command().args(["add", "-b", branch]).assert().success();
+    fs::write(path.join("scratch.txt"), "x").unwrap();
+    let output = repo
+        .command()
+        .args(["rm", "--force", branch])
+        .output()
+        .unwrap();
+    let id = trash_id(&output.stdout);
+
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("other.txt"), "occupied").unwrap();
+    repo.command()
+        .args(["restore", &id])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+}
+
+#[test]
+fn purge_respects_older_than() {
+    let repo = TestRepo::new();
+    let branch = "feature/old";
+    let path = repo.worktree_path_for(branch);
+    repo.command().args(["add", "-b", branch]).assert().success();
+    fs::write(path.join("scratch.txt"), "x").unwrap();
+    let output = repo
+        .command()
+        .args(["rm", "--force", branch])
+        .output()
+        .unwrap();
+    let id = trash_id(&output.stdout);
+
+    repo.command()
+        .args(["trash", "purge", "--older-than", "14d"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Purged 0 trash entries"));
+
+    repo.command()
+        .args(["trash", "purge"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Purged 1 trash entries"));
+
+    let refs = std::process::Command::new("git")
+        .current_dir(repo.path())
+        .args(["for-each-ref", "refs/gwe/trash/"])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&refs.stdout).trim().is_empty());
+    repo.command()
+        .args(["restore", &id])
+        .assert()
+        .failure();
+
+    repo.command()
+        .args(["trash", "purge", "--older-than", "soon"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid duration"));
+}
+
+#[test]
+fn copied_ignored_files_are_kept_in_the_trash() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join(".gitignore"), ".env\nworktree/\n").unwrap();
+    repo.git(&["add", ".gitignore"]);
+    repo.git(&["commit", "-q", "-m", "ignore"]);
+    fs::write(repo.path().join(".env"), "TOKEN=local").unwrap();
+    repo.add_config("gwe.copy.include", ".env");
+
+    let branch = "feature/env";
+    let path = repo.worktree_path_for(branch);
+    repo.command().args(["add", "-b", branch]).assert().success();
+    fs::write(path.join(".env"), "TOKEN=edited").unwrap();
+    fs::write(path.join("scratch.txt"), "x").unwrap();
+
+    let output = repo
+        .command()
+        .args(["rm", "--force", branch])
+        .output()
+        .unwrap();
+    let id = trash_id(&output.stdout);
+
+    repo.command().args(["restore", &id]).assert().success();
+    assert_eq!(fs::read_to_string(path.join(".env")).unwrap(), "TOKEN=edited");
+}